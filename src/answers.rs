@@ -0,0 +1,106 @@
+use std::{fs, io, path::PathBuf};
+
+use crate::{input, runner::Run};
+
+/// The accepted answers for a day's real input, recorded in `answers.txt`
+/// next to its `input.txt`:
+///
+/// ```text
+/// part 1: 13740
+/// part 2:
+/// ####.#..#.###..
+/// ```
+///
+/// An answer either follows its `part N:` header on the same line or, when
+/// it spans several lines, takes all the lines up to the next header.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct Answers {
+    parts: Vec<(u8, String)>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Self {
+        let mut parts: Vec<(u8, String)> = vec![];
+        for line in text.lines() {
+            let header = line
+                .strip_prefix("part ")
+                .and_then(|rest| rest.split_once(':'))
+                .and_then(|(part, rest)| Some((part.parse().ok()?, rest.trim())));
+
+            match (header, parts.last_mut()) {
+                (Some((part, answer)), _) => parts.push((part, answer.to_string())),
+                (None, Some((_, answer))) if answer.is_empty() => *answer = line.to_string(),
+                (None, Some((_, answer))) => *answer += &format!("\n{}", line),
+                (None, None) => {}
+            }
+        }
+
+        Self { parts }
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        self.parts
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, answer)| answer.as_str())
+    }
+}
+
+pub fn path(day: u8) -> PathBuf {
+    input::day_file(day, "answers.txt")
+}
+
+/// The recorded answers for `day`; none at all if it has no answers file yet.
+pub fn load(day: u8) -> io::Result<Answers> {
+    match fs::read_to_string(path(day)) {
+        Ok(text) => Ok(Answers::parse(&text)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e),
+    }
+}
+
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String, found: String },
+    Unrecorded,
+}
+
+pub fn check(run: &Run, answers: &Answers) -> Verdict {
+    let expected = match answers.get(run.part) {
+        Some(expected) => expected,
+        None => return Verdict::Unrecorded,
+    };
+
+    match &run.answer {
+        Ok(answer) if answer.to_string() == expected => Verdict::Correct,
+        answer => Verdict::Wrong {
+            expected: expected.to_string(),
+            found: match answer {
+                Ok(answer) => answer.to_string(),
+                Err(e) => e.to_string(),
+            },
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("part 1: 13740\npart 2:\n##..\n.##.\n");
+        assert_eq!(answers.get(1), Some("13740"));
+        assert_eq!(answers.get(2), Some("##..\n.##."));
+        assert_eq!(answers.get(3), None);
+    }
+
+    #[test]
+    fn test_recorded() {
+        for day in 1..=25 {
+            let answers = load(day).unwrap();
+            assert!(answers.get(1).is_some(), "day {}", day);
+            assert_eq!(answers.get(2).is_some(), day != 25, "day {}", day);
+        }
+    }
+}
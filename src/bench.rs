@@ -0,0 +1,308 @@
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    params::Params,
+    solution::Day,
+};
+
+/// A timed step of solving a day.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum Phase {
+    Parse,
+    Part(u8),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Part(1)),
+            "part2" => Ok(Phase::Part(2)),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarises at least one sample.
+    pub fn of(samples: &mut [Duration]) -> Self {
+        samples.sort();
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Self {
+            runs: samples.len(),
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// How long to keep repeating each phase: `runs` times, or fewer if that
+/// would take more than `budget`. Every phase runs at least once, so slow
+/// days like 16 still get measured.
+pub struct Settings {
+    pub runs: usize,
+    pub budget: Duration,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            runs: 10,
+            budget: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Repeatedly times `f`, which returns whether there was anything to run.
+fn sample<F>(settings: &Settings, mut f: F) -> Result<Option<Stats>, SolveError>
+where
+    F: FnMut() -> Result<bool, SolveError>,
+{
+    let started = Instant::now();
+    let mut samples = vec![];
+    while samples.is_empty() || samples.len() < settings.runs && started.elapsed() < settings.budget
+    {
+        let start = Instant::now();
+        if !f()? {
+            return Ok(None);
+        }
+        samples.push(start.elapsed());
+    }
+
+    Ok(Some(Stats::of(&mut samples)))
+}
+
+/// Times parsing and each of `parts`, skipping parts the day doesn't have.
+/// The parts are timed on an input parsed beforehand.
+pub fn measure(
+    day: &Day,
+    parts: &[u8],
+    input: &str,
+    settings: &Settings,
+) -> Result<Vec<Measurement>, SolveError> {
+    let parsed = day.parse(input)?;
+    let params = Params::default();
+    let mut result = vec![];
+    for phase in [Phase::Parse]
+        .into_iter()
+        .chain(parts.iter().map(|&p| Phase::Part(p)))
+    {
+        let stats = sample(settings, || match phase {
+            Phase::Parse => Ok(day.parse(input).map(|_| true)?),
+            Phase::Part(part) => day
+                .solve_parsed(part, &parsed, &params)
+                .map(|answer| answer.is_some()),
+        })?;
+        if let Some(stats) = stats {
+            result.push(Measurement {
+                day: day.number,
+                phase,
+                stats,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+/// Median times of an earlier run, to compare a new one against.
+#[derive(Default, Debug, PartialEq)]
+pub struct Baseline {
+    medians: HashMap<(u8, Phase), Duration>,
+}
+
+const BASELINE_HEADER: &str = "# day phase min median max (ns)";
+
+impl Baseline {
+    pub fn format(measurements: &[Measurement]) -> String {
+        let mut result = format!("{}\n", BASELINE_HEADER);
+        for m in measurements {
+            result += &format!(
+                "{} {} {} {} {}\n",
+                m.day,
+                m.phase,
+                m.stats.min.as_nanos(),
+                m.stats.median.as_nanos(),
+                m.stats.max.as_nanos()
+            );
+        }
+        result
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut medians = HashMap::new();
+        for line in lines(text).filter(|l| !l.text.is_empty() && !l.text.starts_with('#')) {
+            let (key, median) = parse_entry(&line)?;
+            medians.insert(key, median);
+        }
+        Ok(Self { medians })
+    }
+
+    pub fn get(&self, day: u8, phase: Phase) -> Option<Duration> {
+        self.medians.get(&(day, phase)).copied()
+    }
+}
+
+fn parse_entry(line: &Line) -> Result<((u8, Phase), Duration), ParseError> {
+    match line.text.split(' ').collect::<Vec<&str>>()[..] {
+        [day, phase, _min, median, _max] => {
+            let phase = phase
+                .parse()
+                .map_err(|_| line.error(phase, "parse, part1 or part2"))?;
+            let median = line.parse(median, "nanoseconds")?;
+            Ok((
+                (line.parse(day, "a day")?, phase),
+                Duration::from_nanos(median),
+            ))
+        }
+        _ => Err(line.mismatch("`day phase min median max`")),
+    }
+}
+
+pub fn save(measurements: &[Measurement], path: &Path) -> io::Result<()> {
+    fs::write(path, Baseline::format(measurements))
+}
+
+pub fn load(path: &Path) -> io::Result<Baseline> {
+    Baseline::parse(&fs::read_to_string(path)?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// How much slower, in percent, `stats` is than the baseline's median.
+pub fn change(stats: &Stats, baseline: Duration) -> f64 {
+    (stats.median.as_secs_f64() / baseline.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0) * 100.0
+}
+
+/// Lays measurements out as a table, with the change from `baseline` when
+/// one is given.
+pub fn table(measurements: &[Measurement], baseline: Option<&Baseline>) -> String {
+    let mut result = format!(
+        "{:>3}  {:<5}  {:>4}  {:>12}  {:>12}  {:>12}",
+        "day", "phase", "runs", "min", "median", "max"
+    );
+    if baseline.is_some() {
+        result += &format!("  {:>12}  {:>8}", "baseline", "change");
+    }
+    result += "\n";
+
+    for m in measurements {
+        result += &format!(
+            "{:>3}  {:<5}  {:>4}  {:>12}  {:>12}  {:>12}",
+            m.day,
+            m.phase.to_string(),
+            m.stats.runs,
+            format!("{:.2?}", m.stats.min),
+            format!("{:.2?}", m.stats.median),
+            format!("{:.2?}", m.stats.max),
+        );
+        if let Some(baseline) = baseline {
+            match baseline.get(m.day, m.phase) {
+                Some(median) => {
+                    result += &format!(
+                        "  {:>12}  {:>+7.1}%",
+                        format!("{:.2?}", median),
+                        change(&m.stats, median)
+                    )
+                }
+                None => result += &format!("  {:>12}", "-"),
+            }
+        }
+        result += "\n";
+    }
+    result
+}
+
+/// The measurements whose median got more than `threshold` percent slower
+/// than in `baseline`.
+pub fn regressions<'a>(
+    measurements: &'a [Measurement],
+    baseline: &'a Baseline,
+    threshold: f64,
+) -> impl Iterator<Item = &'a Measurement> {
+    measurements.iter().filter(move |m| {
+        baseline
+            .get(m.day, m.phase)
+            .is_some_and(|median| change(&m.stats, median) > threshold)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::of(&mut [ms(5), ms(1), ms(3)]);
+        assert_eq!((stats.min, stats.median, stats.max), (ms(1), ms(3), ms(5)));
+        assert_eq!(Stats::of(&mut [ms(4), ms(2)]).median, ms(3));
+    }
+
+    #[test]
+    fn test_baseline() {
+        let measurements = [
+            Measurement {
+                day: 16,
+                phase: Phase::Part(2),
+                stats: Stats::of(&mut [ms(100)]),
+            },
+            Measurement {
+                day: 20,
+                phase: Phase::Parse,
+                stats: Stats::of(&mut [ms(10)]),
+            },
+        ];
+        let baseline = Baseline::parse(&Baseline::format(&measurements)).unwrap();
+        assert_eq!(baseline.get(16, Phase::Part(2)), Some(ms(100)));
+
+        let slower = [Measurement {
+            day: 16,
+            phase: Phase::Part(2),
+            stats: Stats::of(&mut [ms(120)]),
+        }];
+        assert_eq!(regressions(&slower, &baseline, 10.0).count(), 1);
+        assert_eq!(regressions(&slower, &baseline, 25.0).count(), 0);
+    }
+}
@@ -0,0 +1,471 @@
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    path::PathBuf,
+    process::exit,
+    time::Duration,
+};
+
+use aoc_2022::{
+    answers::{self, Verdict},
+    bench::{self, Measurement, Settings},
+    budget, days, differential,
+    error::StreamError,
+    input::{self, Source},
+    memory,
+    params::{self, Params},
+    pool,
+    repl::Repl,
+    runner::{self, Run},
+    solution::Day,
+    trace::{self, Level},
+};
+
+const USAGE: &str = "\
+usage: aoc [verify] [DAY|all] [--part 1|2] [--input PATH|-] [--json] [-v|-vv]
+           [--jobs N] [--memory] [--timeout SECS] [--report PATH]
+       aoc DAY [--PARAM VALUE]... (see `aoc DAY --help` for the day's own)
+       aoc lint [DAY|all] [--input PATH|-]
+       aoc repl DAY [--input PATH]
+       aoc stats DAY [--input PATH|-] [--PARAM VALUE]...
+       aoc bench [DAY|all] [--part 1|2] [--runs N] [--budget SECS]
+                 [--save PATH] [--compare PATH] [--threshold PERCENT]
+       aoc generate DAY [--seed N] [--size N]
+       aoc diff DAY [--part 1|2] [--seed N] [--size N] [--cases N]";
+
+enum Command {
+    Run,
+    Verify,
+    Lint,
+    Repl,
+    Stats,
+    Bench,
+    Generate,
+    Diff,
+}
+
+/// Options of `aoc bench`.
+struct BenchOptions {
+    settings: Settings,
+    save: Option<PathBuf>,
+    compare: Option<PathBuf>,
+    threshold: f64,
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    exit(2);
+}
+
+/// Compares `runs` with the recorded answers, reporting every difference.
+/// Returns whether they all matched.
+fn verify(runs: &[Run]) -> bool {
+    let (mut correct, mut wrong, mut unrecorded) = (0, 0, 0);
+    for run in runs {
+        let recorded = match answers::load(run.day) {
+            Ok(recorded) => recorded,
+            Err(e) => {
+                eprintln!("day {}: cannot read answers: {}", run.day, e);
+                exit(1);
+            }
+        };
+
+        match answers::check(run, &recorded) {
+            Verdict::Correct => correct += 1,
+            Verdict::Unrecorded => {
+                println!("day {}, part {}: no recorded answer", run.day, run.part);
+                unrecorded += 1;
+            }
+            Verdict::Wrong { expected, found } => {
+                if expected.contains('\n') || found.contains('\n') {
+                    println!("day {}, part {}: expected", run.day, run.part);
+                    println!("{}\nfound\n{}", expected, found);
+                } else {
+                    println!(
+                        "day {}, part {}: expected {}, found {}",
+                        run.day, run.part, expected, found
+                    );
+                }
+                wrong += 1;
+            }
+        }
+    }
+
+    println!(
+        "{} correct, {} wrong, {} unrecorded",
+        correct, wrong, unrecorded
+    );
+    wrong == 0
+}
+
+/// Reports every problem with the layout of each input. Returns whether
+/// there were none.
+fn lint(inputs: &[(Day, String)]) -> bool {
+    let mut count = 0;
+    for (day, input) in inputs {
+        for problem in day.lint(input) {
+            println!("{}", problem);
+            count += 1;
+        }
+    }
+    if count == 0 {
+        println!("no problems in {} inputs", inputs.len());
+    }
+    count == 0
+}
+
+/// Times every selected day, then saves and/or compares against a baseline.
+/// Returns whether nothing got slower than the threshold allows.
+fn bench(inputs: &[(Day, String)], parts: &[u8], options: &BenchOptions) -> bool {
+    let mut measurements: Vec<Measurement> = vec![];
+    for (day, input) in inputs {
+        match bench::measure(day, parts, input, &options.settings) {
+            Ok(m) => measurements.extend(m),
+            Err(e) => {
+                eprintln!("{}", e);
+                exit(1);
+            }
+        }
+    }
+
+    let baseline = options
+        .compare
+        .as_ref()
+        .map(|path| match bench::load(path) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                eprintln!("cannot read baseline `{}`: {}", path.display(), e);
+                exit(1);
+            }
+        });
+    print!("{}", bench::table(&measurements, baseline.as_ref()));
+
+    if let Some(path) = &options.save {
+        if let Err(e) = bench::save(&measurements, path) {
+            eprintln!("cannot save baseline `{}`: {}", path.display(), e);
+            exit(1);
+        }
+    }
+
+    let mut regressed = false;
+    if let Some(baseline) = &baseline {
+        for m in bench::regressions(&measurements, baseline, options.threshold) {
+            println!(
+                "day {} {}: more than {}% slower than the baseline",
+                m.day, m.phase, options.threshold
+            );
+            regressed = true;
+        }
+    }
+    !regressed
+}
+
+/// Checks a day against its reference on `cases` generated inputs, starting
+/// at `seed`. Returns whether they agreed on every one.
+fn diff(day: &Day, parts: &[u8], seed: u64, cases: u64, size: usize) -> bool {
+    match differential::search(day, parts, seed..seed + cases, size) {
+        Ok(0) => {
+            eprintln!(
+                "day {} has no reference solution to compare with",
+                day.number
+            );
+            false
+        }
+        Ok(checked) => {
+            println!("day {}: {} comparisons agreed", day.number, checked);
+            true
+        }
+        Err(d) => {
+            println!(
+                "day {}, part {}, seed {}: reference {}, solution {}",
+                d.day, d.part, d.seed, d.reference, d.solution
+            );
+            print!("minimised input:\n{}", d.input);
+            false
+        }
+    }
+}
+
+/// Prints the day's report on its input, read as it streams in. Returns
+/// whether the day has one and could read the input.
+fn stats(day: &Day, source: &Source, params: &Params) -> bool {
+    let mut input = match input::open(day.number, source) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+    match day.stats(&mut input, params) {
+        Some(Ok(report)) => {
+            println!("{}", report);
+            true
+        }
+        Some(Err(StreamError::Read(e))) => {
+            eprintln!("{}", input.error(e));
+            false
+        }
+        Some(Err(e)) => {
+            eprintln!("{}", e);
+            false
+        }
+        None => {
+            eprintln!("day {} has no statistics to report", day.number);
+            false
+        }
+    }
+}
+
+fn parse_arg<T: std::str::FromStr>(value: Option<String>, message: &str) -> T {
+    match value.and_then(|v| v.parse().ok()) {
+        Some(value) => value,
+        None => fail(message),
+    }
+}
+
+fn main() {
+    let mut command = Command::Run;
+    let mut bench_options = BenchOptions {
+        settings: Settings::default(),
+        save: None,
+        compare: None,
+        threshold: 10.0,
+    };
+    let mut json_output = false;
+    let mut report: Option<PathBuf> = None;
+    let mut seed = 0;
+    let mut size = None;
+    let mut cases = 100;
+    let mut day = None;
+    let mut parts = vec![1, 2];
+    let mut source = Source::Bundled;
+    let mut help = false;
+    let mut custom = vec![];
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => match args.next().as_deref() {
+                Some("1") => parts = vec![1],
+                Some("2") => parts = vec![2],
+                _ => fail("--part expects 1 or 2"),
+            },
+            "--input" => match args.next() {
+                Some(path) => source = Source::from_arg(&path),
+                None => fail("--input expects a path, or - for stdin"),
+            },
+            "-h" | "--help" => help = true,
+            "--runs" => {
+                bench_options.settings.runs = parse_arg(args.next(), "--runs expects a count")
+            }
+            "--budget" => {
+                let secs = parse_arg(args.next(), "--budget expects seconds");
+                match Duration::try_from_secs_f64(secs) {
+                    Ok(budget) => bench_options.settings.budget = budget,
+                    Err(_) => fail("--budget expects seconds"),
+                }
+            }
+            "--save" => bench_options.save = Some(parse_arg(args.next(), "--save expects a path")),
+            "--compare" => {
+                bench_options.compare = Some(parse_arg(args.next(), "--compare expects a path"))
+            }
+            "--threshold" => {
+                bench_options.threshold = parse_arg(args.next(), "--threshold expects a percentage")
+            }
+            "--seed" => seed = parse_arg(args.next(), "--seed expects a number"),
+            "--size" => size = Some(parse_arg(args.next(), "--size expects a number")),
+            "--cases" => cases = parse_arg(args.next(), "--cases expects a count"),
+            "--json" => json_output = true,
+            "--report" => report = Some(parse_arg(args.next(), "--report expects a path")),
+            "--jobs" => pool::set_workers(parse_arg(args.next(), "--jobs expects a count")),
+            "--memory" => memory::set_tracking(true),
+            "--timeout" => {
+                let secs = parse_arg(args.next(), "--timeout expects seconds above 0");
+                match Duration::try_from_secs_f64(secs) {
+                    Ok(timeout) if !timeout.is_zero() => budget::set_timeout(timeout),
+                    _ => fail("--timeout expects seconds above 0"),
+                }
+            }
+            "-v" => trace::set_level(Level::Steps),
+            "-vv" => trace::set_level(Level::States),
+            "verify" => command = Command::Verify,
+            "lint" => command = Command::Lint,
+            "repl" => command = Command::Repl,
+            "stats" => command = Command::Stats,
+            "bench" => command = Command::Bench,
+            "generate" => command = Command::Generate,
+            "diff" => command = Command::Diff,
+            "all" => day = None,
+            name if name.starts_with("--") => match args.next() {
+                Some(value) => custom.push((name[2..].to_string(), value)),
+                None => fail(&format!("unknown option `{}`", name)),
+            },
+            _ => match arg.parse::<u8>().ok().and_then(days::get) {
+                Some(d) => day = Some(d),
+                None => fail(&format!("unknown day `{}`", arg)),
+            },
+        }
+    }
+
+    if help {
+        println!("{}", USAGE);
+        if let Some(d) = day.filter(|d| !d.params.is_empty()) {
+            print!("\nday {} options:\n{}", d.number, params::help(d.params));
+        }
+        return;
+    }
+
+    let mut params = Params::default();
+    if !custom.is_empty() {
+        let Some(d) = day.filter(|_| matches!(command, Command::Run | Command::Stats)) else {
+            fail("day options can only be given when running or reporting on a single day");
+        };
+        for (name, value) in &custom {
+            if let Err(e) = params.set(d.params, name, value) {
+                fail(&e);
+            }
+        }
+    }
+
+    if let Command::Generate = command {
+        match day {
+            Some(d) => print!("{}", d.generate(seed, size.unwrap_or(10))),
+            None => fail("generate needs a single day"),
+        }
+        return;
+    }
+
+    if let Command::Diff = command {
+        let Some(d) = day else {
+            fail("diff needs a single day");
+        };
+        if !diff(&d, &parts, seed, cases, size.unwrap_or(5)) {
+            exit(1);
+        }
+        return;
+    }
+
+    if let Command::Repl = command {
+        let Some(d) = day else {
+            fail("repl needs a single day");
+        };
+        if let Source::Stdin = source {
+            fail("repl reads its commands from stdin, so --input expects a path");
+        }
+        let input = match input::load(d.number, &source) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{}", e);
+                exit(1);
+            }
+        };
+        let mut repl = match Repl::new(d, &input) {
+            Ok(repl) => repl,
+            Err(e) => {
+                eprintln!("{}", e);
+                exit(1);
+            }
+        };
+        let stdin = io::stdin();
+        let prompt = stdin.is_terminal();
+        if let Err(e) = repl.run(stdin.lock(), &mut io::stdout(), prompt) {
+            eprintln!("{}", e);
+            exit(1);
+        }
+        return;
+    }
+
+    if let Command::Stats = command {
+        let Some(d) = day else {
+            fail("stats needs a single day");
+        };
+        if !stats(&d, &source, &params) {
+            exit(1);
+        }
+        return;
+    }
+
+    if matches!(command, Command::Verify) && !matches!(source, Source::Bundled) {
+        fail("verify checks the bundled inputs, not --input");
+    }
+
+    let selected = match day {
+        Some(d) => vec![d],
+        None if matches!(source, Source::Bundled) => days::all(),
+        None => fail("--input needs a single day"),
+    };
+
+    // An input given on the command line can be too large to load, so a
+    // day that can parses it as it is read.
+    let streamed = match selected[..] {
+        [d] if matches!(command, Command::Run) && !matches!(source, Source::Bundled) => Some(d),
+        _ => None,
+    };
+    let mut inputs = vec![];
+    for d in selected.into_iter().filter(|_| streamed.is_none()) {
+        match input::load(d.number, &source) {
+            Ok(input) => inputs.push((d, input)),
+            Err(e) => {
+                eprintln!("{}", e);
+                exit(1);
+            }
+        }
+    }
+
+    if let Command::Lint = command {
+        if !lint(&inputs) {
+            exit(1);
+        }
+        return;
+    }
+
+    if let Command::Bench = command {
+        if !bench(&inputs, &parts, &bench_options) {
+            exit(1);
+        }
+        return;
+    }
+
+    let runs = if let Some(d) = streamed {
+        match runner::run_streamed(&d, &parts, &source, &params) {
+            Ok(runs) => runs,
+            Err(e) => {
+                eprintln!("{}", e);
+                exit(1);
+            }
+        }
+    } else {
+        runner::run_all(&inputs, &parts, &params)
+    };
+
+    if let Some(path) = &report {
+        if let Err(e) = fs::write(path, runner::report(&runs)) {
+            eprintln!("cannot write report `{}`: {}", path.display(), e);
+            exit(1);
+        }
+    }
+
+    if let Command::Verify = command {
+        if !verify(&runs) {
+            exit(1);
+        }
+        return;
+    }
+
+    if json_output {
+        for run in &runs {
+            println!("{}", runner::json(run));
+        }
+    } else {
+        print!("{}", runner::table(&runs));
+    }
+
+    let mut failed = false;
+    for e in runner::errors(&runs) {
+        eprintln!("{}", e);
+        failed = true;
+    }
+    if failed {
+        exit(1);
+    }
+}
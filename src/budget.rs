@@ -0,0 +1,145 @@
+use std::{
+    cell::{Cell, RefCell},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// How long a part may run, and a flag that stops it sooner from another
+/// thread. Long searches check it with [`spent`] and stop early with the
+/// best answer they have.
+#[derive(Clone, Default)]
+pub struct Budget {
+    deadline: Option<Instant>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Budget {
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    pub fn within(limit: Duration) -> Self {
+        Self {
+            deadline: Instant::now().checked_add(limit),
+            ..Self::default()
+        }
+    }
+
+    /// Stops the parts running under this budget, or any of its clones, at
+    /// their next check.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// A budget as one call of [`limit`] runs under it, remembering whether
+/// anything running there found it spent.
+#[derive(Clone)]
+pub(crate) struct Running {
+    budget: Budget,
+    stopped: Arc<AtomicBool>,
+}
+
+/// How many checks read the clock once between them; reading it is what
+/// makes a check cost anything.
+const CLOCK_EVERY: u32 = 64;
+
+impl Running {
+    fn spent(&self) -> bool {
+        if self.stopped.load(Ordering::Relaxed) {
+            return true;
+        }
+        let late = self.budget.deadline.is_some_and(|deadline| {
+            let checks = CHECKS.get().wrapping_add(1);
+            CHECKS.set(checks);
+            checks.is_multiple_of(CLOCK_EVERY) && Instant::now() >= deadline
+        });
+        let spent = late || self.budget.cancelled.load(Ordering::Relaxed);
+        if spent {
+            self.stopped.store(true, Ordering::Relaxed);
+        }
+        spent
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Running>> = const { RefCell::new(None) };
+    /// Checks made on this thread, for reading the clock every [`CLOCK_EVERY`].
+    static CHECKS: Cell<u32> = const { Cell::new(0) };
+}
+
+pub(crate) fn current() -> Option<Running> {
+    CURRENT.with_borrow(Clone::clone)
+}
+
+/// Lets a [`pool`](crate::pool) thread run under its caller's budget.
+pub(crate) fn set_current(running: Option<Running>) {
+    CURRENT.set(running);
+}
+
+/// Runs `f` under `budget`, on this thread and on the [`pool`](crate::pool)
+/// threads it starts. Returns whether `f` found the budget spent, and so
+/// stopped short of its answer.
+pub fn limit<T>(budget: &Budget, f: impl FnOnce() -> T) -> (T, bool) {
+    let running = Running {
+        budget: budget.clone(),
+        stopped: Arc::default(),
+    };
+    let outer = CURRENT.replace(Some(running.clone()));
+    let result = f();
+    CURRENT.set(outer);
+    (result, running.stopped.load(Ordering::Relaxed))
+}
+
+/// Whether the budget the caller runs under is spent, in which case it
+/// should stop with what it has. Always `false` outside [`limit`].
+pub fn spent() -> bool {
+    CURRENT.with_borrow(|running| running.as_ref().is_some_and(Running::spent))
+}
+
+/// Nanoseconds each part may run for, as `--timeout` asks; 0 for no limit.
+static TIMEOUT: AtomicU64 = AtomicU64::new(0);
+
+/// Gives each part `timeout`, or no limit if it is zero. Anything past the
+/// 584 years a `u64` of nanoseconds holds is no limit either.
+pub fn set_timeout(timeout: Duration) {
+    let nanos = u64::try_from(timeout.as_nanos()).unwrap_or(0);
+    TIMEOUT.store(nanos, Ordering::Relaxed);
+}
+
+/// A budget for a part the runner is about to start.
+pub fn for_part() -> Budget {
+    match TIMEOUT.load(Ordering::Relaxed) {
+        0 => Budget::unlimited(),
+        nanos => Budget::within(Duration::from_nanos(nanos)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up until the budget is spent.
+    fn count() -> u64 {
+        let mut n = 0;
+        while !spent() && n < 1_000_000 {
+            n += 1;
+        }
+        n
+    }
+
+    #[test]
+    fn test_limit() {
+        assert_eq!(limit(&Budget::unlimited(), count), (1_000_000, false));
+        let (n, stopped) = limit(&Budget::within(Duration::ZERO), count);
+        assert!(n < CLOCK_EVERY as u64 && stopped, "{}", n);
+
+        let budget = Budget::unlimited();
+        budget.cancel();
+        assert_eq!(limit(&budget.clone(), count), (0, true));
+        assert!(!spent());
+    }
+}
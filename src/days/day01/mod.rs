@@ -0,0 +1,581 @@
+use std::{cmp::Reverse, collections::BinaryHeap, io::BufRead};
+
+use crate::{
+    error::{lines, Line, ParseError, SolveError, StreamError},
+    generate::Rng,
+    lint::Section,
+    params::{Param, Params},
+    repl::{Model, Query},
+    solution::{Answer, Solution},
+};
+
+use stats::Stats;
+
+pub mod stats;
+pub mod stream;
+
+/// How [`parse_input`] treats lines that aren't calorie counts. Either way,
+/// the calories together must fit in a `usize`, so that no sum of them
+/// overflows.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mode {
+    /// Only an empty line separates elves, and only one at a time, since a
+    /// second would stand for an elf without snacks; blank lines at the end
+    /// are let through. Any other line must be a calorie count.
+    Strict,
+    /// Any line that isn't a number ends an elf, so a typo like `12a4` splits
+    /// one in two; blank lines in a row give elves without snacks.
+    Lenient,
+}
+
+impl Mode {
+    /// The mode `--lenient` asks for.
+    pub fn of(params: &Params) -> Self {
+        match params.get::<u8>(&LENIENT) {
+            0 => Mode::Strict,
+            _ => Mode::Lenient,
+        }
+    }
+}
+
+/// The calories on one line of the inventory, or `None` for a line that
+/// ends an elf.
+fn read_line(line: &Line, mode: Mode) -> Result<Option<usize>, ParseError> {
+    match (mode, line.text) {
+        (_, "") => Ok(None),
+        (Mode::Lenient, text) => Ok(text.parse().ok()),
+        (Mode::Strict, text) if text.bytes().all(|b| b.is_ascii_digit()) => {
+            let expected = format!("a calorie count of at most {}", usize::MAX);
+            line.parse(text, &expected).map(Some)
+        }
+        (Mode::Strict, _) => Err(line.mismatch("a calorie count or an empty line")),
+    }
+}
+
+/// What a line of the inventory does to the elf being read.
+enum Entry {
+    Snack(usize),
+    NextElf,
+    /// A blank line in a row, which only matters if a snack follows.
+    Skip,
+}
+
+/// Reads an inventory line by line, checking it as its [`Mode`] asks, for
+/// both [`parse_input`] and [`stream::parse`]. A line with a problem is
+/// skipped once it is noted, so that every problem is found in one pass.
+struct Reader {
+    mode: Mode,
+    /// Calories of all the snacks so far.
+    total: usize,
+    /// Snacks of the elf being read.
+    snacks: usize,
+    /// The first blank line in a row since the last snack; harmless at the
+    /// end of the input, so it is only reported if a snack follows.
+    extra_blank: Option<ParseError>,
+    lines: usize,
+    /// Every problem so far, in input order.
+    problems: Vec<ParseError>,
+}
+
+impl Reader {
+    fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            total: 0,
+            snacks: 0,
+            extra_blank: None,
+            lines: 0,
+            problems: vec![],
+        }
+    }
+
+    fn read(&mut self, line: &Line) -> Entry {
+        self.lines = line.number;
+        let strict = self.mode == Mode::Strict;
+        match read_line(line, self.mode) {
+            Err(problem) => {
+                self.problems.push(problem);
+                Entry::Skip
+            }
+            Ok(Some(calories)) => {
+                if let Some(blank) = self.extra_blank.take() {
+                    self.problems.push(blank);
+                }
+                match self.total.checked_add(calories) {
+                    Some(total) => self.total = total,
+                    None => {
+                        self.problems.push(
+                            line.error(line.text, "calories that keep the total within a `usize`"),
+                        );
+                        return Entry::Skip;
+                    }
+                }
+                self.snacks += 1;
+                Entry::Snack(calories)
+            }
+            Ok(None) if strict && self.snacks == 0 => {
+                if self.extra_blank.is_none() {
+                    self.extra_blank = Some(line.error("", "a calorie count"));
+                }
+                Entry::Skip
+            }
+            Ok(None) => {
+                self.snacks = 0;
+                Entry::NextElf
+            }
+        }
+    }
+
+    /// Whether the elf being read at the end of the input counts as one,
+    /// after `ended` others: in strict mode, only if it has a snack. Or
+    /// every problem found, if there were any.
+    fn finish(mut self, ended: usize) -> Result<bool, Vec<ParseError>> {
+        let counts = match self.mode {
+            Mode::Strict if self.snacks == 0 && ended == 0 => {
+                // Only worth saying if nothing else was wrong.
+                if self.problems.is_empty() {
+                    let problem = ParseError::end_of_input(self.lines, "a calorie count");
+                    self.problems.push(problem);
+                }
+                false
+            }
+            Mode::Strict => self.snacks > 0,
+            Mode::Lenient => true,
+        };
+        if self.problems.is_empty() {
+            Ok(counts)
+        } else {
+            Err(self.problems)
+        }
+    }
+}
+
+/// The calories of each elf's snacks, or every problem with the inventory.
+pub fn parse_input(input: &str, mode: Mode) -> Result<Vec<Vec<usize>>, Vec<ParseError>> {
+    let mut reader = Reader::new(mode);
+    let mut elves = vec![vec![]];
+    for line in lines(input) {
+        match reader.read(&line) {
+            Entry::Snack(calories) => elves.last_mut().unwrap().push(calories),
+            Entry::NextElf => elves.push(vec![]),
+            Entry::Skip => {}
+        }
+    }
+    if !reader.finish(elves.len() - 1)? {
+        elves.pop();
+    }
+    Ok(elves)
+}
+
+/// An elf among those carrying the most calories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    /// The elf's place in the input, counting from 1.
+    pub number: usize,
+    pub calories: usize,
+}
+
+/// The elves carrying the most calories, and how many they carry together.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Top {
+    /// Most calories first; of elves carrying as many, the earlier one.
+    pub elves: Vec<Elf>,
+    pub calories: usize,
+}
+
+/// The `n` elves carrying the most calories among those seen so far. A
+/// min-heap holds the best `n`, so the others are never sorted; it grows as
+/// elves come, since `n` may be far more than there are.
+struct Ranking {
+    n: usize,
+    best: BinaryHeap<Reverse<(usize, Reverse<usize>)>>,
+}
+
+impl Ranking {
+    fn new(n: usize) -> Self {
+        Self {
+            n,
+            best: BinaryHeap::new(),
+        }
+    }
+
+    fn push(&mut self, elf: Elf) {
+        self.best.push(Reverse((elf.calories, Reverse(elf.number))));
+        if self.best.len() > self.n {
+            self.best.pop();
+        }
+    }
+
+    fn into_top(self) -> Top {
+        let elves = self
+            .best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((calories, Reverse(number)))| Elf { number, calories })
+            .collect::<Vec<_>>();
+        Top {
+            calories: elves.iter().map(|elf| elf.calories).sum(),
+            elves,
+        }
+    }
+}
+
+/// The `n` elves carrying the most calories, or all of them if there are
+/// fewer.
+pub fn top(elves: &[Vec<usize>], n: usize) -> Top {
+    let mut ranking = Ranking::new(n);
+    for (i, snacks) in elves.iter().enumerate() {
+        ranking.push(Elf {
+            number: i + 1,
+            calories: snacks.iter().sum(),
+        });
+    }
+    ranking.into_top()
+}
+
+/// The inventory as the parts read it: strictly, unless `--lenient` is set.
+/// Whether it is set is only known once a part runs, so an input the strict
+/// reading rejects is kept as the lenient one reads it, along with every
+/// problem the strict one found. One parsed by [`stream::parse`] only keeps
+/// the best three elves.
+#[derive(Debug)]
+pub struct Inventory {
+    elves: Vec<Vec<usize>>,
+    problems: Vec<ParseError>,
+}
+
+impl Inventory {
+    /// Fails only if the lenient reading does too, with its first problem.
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        match parse_input(input, Mode::Strict) {
+            Ok(elves) => Ok(Self {
+                elves,
+                problems: vec![],
+            }),
+            Err(problems) => Ok(Self {
+                elves: parse_input(input, Mode::Lenient).map_err(|mut e| e.remove(0))?,
+                problems,
+            }),
+        }
+    }
+
+    /// The elves, or why the input can't be read as `params` asks. Where the
+    /// strict reading succeeds, the lenient one only adds elves without
+    /// snacks for blank lines at the end, so it is used for both.
+    fn elves(&self, params: &Params) -> Result<&[Vec<usize>], SolveError> {
+        match Mode::of(params) {
+            Mode::Strict if !self.problems.is_empty() => {
+                Err(SolveError::Problems(self.problems.clone()))
+            }
+            _ => Ok(&self.elves),
+        }
+    }
+}
+
+fn process_part1(elves: &[Vec<usize>]) -> usize {
+    top(elves, 1).calories
+}
+
+fn process_part2(elves: &[Vec<usize>]) -> usize {
+    top(elves, 3).calories
+}
+
+/// The elves' snacks as `--lenient` reads them, for `aoc repl`; or, where
+/// the strict reading fails, the problems it found, which every query gives.
+struct Explorer {
+    elves: Result<Vec<Vec<usize>>, String>,
+    /// What `stats` splits its histogram into when not told: `--buckets`.
+    buckets: usize,
+}
+
+impl Model for Explorer {
+    fn queries(&self) -> &'static [Query] {
+        &[
+            Query {
+                name: "top",
+                args: &["[N]"],
+                help: "the N (3) elves carrying the most calories, and their total",
+            },
+            Query {
+                name: "elves",
+                args: &[],
+                help: "each elf's snacks and calories",
+            },
+            Query {
+                name: "stats",
+                args: &["[BUCKETS]"],
+                help: "averages, percentiles and a BUCKETS (--buckets) histogram of calories",
+            },
+        ]
+    }
+
+    fn query(&self, name: &str, args: &[&str]) -> Result<String, String> {
+        let number = |default| match args.first() {
+            Some(n) => n.parse().map_err(|_| format!("{} should be a number", n)),
+            None => Ok(default),
+        };
+        let elves = || self.elves.as_deref().map_err(String::clone);
+        match name {
+            "top" => {
+                let top = top(elves()?, number(3)?);
+                Ok(top
+                    .elves
+                    .iter()
+                    .map(|elf| format!("elf {}: {}\n", elf.number, elf.calories))
+                    .collect::<String>()
+                    + &format!("total: {}", top.calories))
+            }
+            "elves" => Ok(Stats::of(elves()?)
+                .elves
+                .iter()
+                .enumerate()
+                .map(|(i, &(snacks, calories))| {
+                    let plural = if snacks == 1 { "" } else { "s" };
+                    format!("elf {}: {} snack{}, {} calories", i + 1, snacks, plural, calories)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")),
+            "stats" => Ok(Stats::of(elves()?).report(number(self.buckets)?)),
+            _ => Err(format!("unknown query `{}`", name)),
+        }
+    }
+}
+
+/// `size` elves, each carrying a few snacks.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            (0..rng.range(1..=15))
+                .map(|_| format!("{}\n", rng.range(1000..=60000)))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+const LENIENT: Param = Param {
+    name: "lenient",
+    default: 0,
+    range: 0..=1,
+    help: "1 to end an elf at any line that isn't a calorie count",
+};
+
+const BUCKETS: Param = Param {
+    name: "buckets",
+    default: 10,
+    range: 1..=stats::MAX_BUCKETS as i64,
+    help: "ranges in the calorie histogram of `aoc stats` and the REPL",
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 1;
+    const PARAMS: &'static [Param] = &[LENIENT, BUCKETS];
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a calorie count",
+        allowed: "a digit",
+        chars: |c| c.is_ascii_digit(),
+        repeats: true,
+        ..Section::TEXT
+    }];
+
+    type Parsed = Inventory;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Inventory::parse(input)
+    }
+
+    fn parse_stream(input: &mut dyn BufRead) -> Option<Result<Self::Parsed, StreamError>> {
+        Some(stream::parse(input))
+    }
+
+    fn part1(inventory: &Self::Parsed, params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(inventory.elves(params)?).into())
+    }
+
+    fn part2(inventory: &Self::Parsed, params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(inventory.elves(params)?).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn stats(input: &mut dyn BufRead, params: &Params) -> Option<Result<String, StreamError>> {
+        let stats = stream::stats(input, Mode::of(params));
+        Some(stats.map(|stats| stats.report(params.get(&BUCKETS))))
+    }
+
+    fn model(inventory: &Self::Parsed, params: &Params) -> Option<Box<dyn Model>> {
+        Some(Box::new(Explorer {
+            // The problems only need the day; no part is being solved.
+            elves: inventory
+                .elves(params)
+                .map(<[_]>::to_vec)
+                .map_err(|e| e.in_part(Self::DAY, 0).to_string()),
+            buckets: params.get(&BUCKETS),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days, repl::Repl};
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT, Mode::Strict).unwrap());
+        assert_eq!(result, 24000);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT, Mode::Strict).unwrap());
+        assert_eq!(result, 45000);
+    }
+
+    #[test]
+    fn test_strict() {
+        let error = |input: &str| parse_input(input, Mode::Strict).err();
+        let lines = |input: &str| error(input).map(|e| e.iter().map(|e| e.line).collect::<Vec<_>>());
+        assert_eq!(
+            error("1000\n\n12a4\n3000"),
+            Some(vec![ParseError::new(3, 1, "a calorie count or an empty line", "`12a4`")])
+        );
+        assert_eq!(
+            error("1\n\n\n2").map(|e| (e[0].line, e[0].expected.clone())),
+            Some((3, "a calorie count".to_string()))
+        );
+        assert_eq!(lines(&format!("{}\n\n{}", usize::MAX, 1)), Some(vec![3]));
+        assert_eq!(lines(&format!("{}0", usize::MAX)), Some(vec![1]));
+        assert_eq!(error("\n\n").map(|e| e[0].found.clone()), Some("end of input".to_string()));
+        assert_eq!(
+            parse_input("1\n2\n\n3\n\n\n", Mode::Strict),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+
+        // Every problem is reported, not just the first.
+        assert_eq!(lines("1000\n12a4\n3000\n\n5x00\n\n\n7"), Some(vec![2, 5, 6]));
+        assert_eq!(lines("x\n"), Some(vec![1]));
+    }
+
+    #[test]
+    fn test_lenient() {
+        let elves = parse_input("1000\n12a4\n3000\n\n\n5", Mode::Lenient).unwrap();
+        assert_eq!(elves, [vec![1000], vec![3000], vec![], vec![5]]);
+        let error = parse_input(&format!("{}\nx\n1", usize::MAX), Mode::Lenient).err();
+        assert_eq!(error.map(|e| e[0].line), Some(3));
+    }
+
+    #[test]
+    fn test_lenient_param() {
+        let inventory = Solver::parse("1000\n12a4\n3000\n\n\n5").unwrap();
+        let mut params = Params::default();
+        assert_eq!(
+            Solver::part1(&inventory, &params),
+            Err(SolveError::Problems(vec![
+                ParseError::new(2, 1, "a calorie count or an empty line", "`12a4`"),
+                ParseError::new(5, 1, "a calorie count", "end of line"),
+            ]))
+        );
+        params.set(Solver::PARAMS, "lenient", "1").unwrap();
+        assert_eq!(Solver::part1(&inventory, &params), Ok(Answer::Integer(3000)));
+        assert_eq!(Solver::part2(&inventory, &params), Ok(Some(Answer::Integer(4005))));
+
+        let inventory = Solver::parse(INPUT).unwrap();
+        assert_eq!(Solver::part2(&inventory, &params), Ok(Some(Answer::Integer(45000))));
+    }
+
+    #[test]
+    fn test_top() {
+        let elves = parse_input(INPUT, Mode::Strict).unwrap();
+        let numbers = |top: Top| top.elves.iter().map(|e| e.number).collect::<Vec<_>>();
+        assert_eq!(numbers(top(&elves, 3)), [4, 3, 5]);
+        assert_eq!(top(&elves, 9).calories, 55000);
+        assert_eq!(top(&elves, usize::MAX).calories, 55000);
+        assert_eq!(top(&elves, 0), Top { elves: vec![], calories: 0 });
+
+        let tied = parse_input("5\n\n7\n\n2\n3\n\n7", Mode::Strict).unwrap();
+        assert_eq!(numbers(top(&tied, 3)), [2, 4, 1]);
+    }
+
+    #[test]
+    fn test_queries() {
+        let explorer = Explorer {
+            elves: Ok(parse_input(INPUT, Mode::Strict).unwrap()),
+            buckets: 10,
+        };
+        assert_eq!(
+            explorer.query("top", &["2"]),
+            Ok("elf 4: 24000\nelf 3: 11000\ntotal: 35000".to_string())
+        );
+        assert!(explorer.query("top", &["x"]).is_err());
+        assert_eq!(explorer.query("mean", &[]), Err("unknown query `mean`".to_string()));
+        assert!(explorer
+            .query("top", &["100000000000000"])
+            .unwrap()
+            .ends_with("total: 55000"));
+        assert_eq!(
+            explorer.query("elves", &[]).unwrap().lines().nth(3),
+            Some("elf 4: 3 snacks, 24000 calories")
+        );
+    }
+
+    #[test]
+    fn test_repl_strict() {
+        let mut repl = Repl::new(days::get(1).unwrap(), "1000\n12a4\n3000\n\n5000").unwrap();
+        let problem = "day 1, line 2, column 1: expected a calorie count or an empty line, \
+                       found `12a4`";
+        assert_eq!(repl.eval("stats"), Err(problem.to_string()));
+        assert_eq!(repl.eval("top 2"), Err(problem.to_string()));
+        assert_eq!(repl.eval("part 1"), Err(problem.to_string()));
+        repl.eval("set lenient 1").unwrap();
+        assert!(repl.eval("stats").unwrap().starts_with("elves: 3\n"));
+        assert!(repl.eval("top 2").unwrap().ends_with("total: 8000\n"));
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::of(&parse_input(INPUT, Mode::Strict).unwrap());
+        assert_eq!((stats.snacks(), stats.calories()), (10, 55000));
+        assert_eq!((stats.mean(), stats.median()), (Some(11000.0), Some(10000.0)));
+        assert_eq!(
+            [10, 50, 80, 100].map(|p| stats.percentile(p)),
+            [4000, 10000, 11000, 24000].map(Some)
+        );
+        assert_eq!(
+            stats.histogram(3, 4),
+            " 4000-10666 #### 3\n10667-17333 ##   1\n17334-24000 ##   1"
+        );
+        assert_eq!(Stats::of(&[]).report(10), "no elves");
+
+        let widest = parse_input("0\n\n18446744073709551615", Mode::Strict).unwrap();
+        assert_eq!(
+            Stats::of(&widest).histogram(2, 1),
+            concat!(
+                "                   0- 9223372036854775807 # 1\n",
+                " 9223372036854775808-18446744073709551615 # 1"
+            )
+        );
+        assert!(Stats::of(&widest).report(10).contains("median: 9223372036854775808.0"));
+        let spread = (1..=1000).map(|i| vec![i * 1_000_000]).collect::<Vec<_>>();
+        let histogram = Stats::of(&spread).histogram(1_000_000_000, 10);
+        assert_eq!(histogram.lines().count(), stats::MAX_BUCKETS);
+    }
+
+    #[test]
+    fn test_large_input() {
+        // 200000 elves carrying 1, 2, ..., 9, 1, 2, ... calories.
+        let input = (0..200_000)
+            .map(|i| format!("{}\n", i % 9 + 1))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let elves = parse_input(&input, Mode::Strict).unwrap();
+        assert_eq!(process_part1(&elves), 9);
+        assert_eq!(process_part2(&elves), 27);
+    }
+}
@@ -0,0 +1,120 @@
+//! How the calories are spread among the elves, for planning beyond the
+//! puzzle's top three.
+
+/// The most ranges a histogram is split into, however many are asked for,
+/// as each takes a line and the counts are all held at once.
+pub const MAX_BUCKETS: usize = 100;
+
+/// Each elf's snacks, counted and summed.
+pub struct Stats {
+    /// Snacks and calories of each elf, in input order.
+    pub elves: Vec<(usize, usize)>,
+    /// The elves' calories, smallest first.
+    sorted: Vec<usize>,
+}
+
+impl Stats {
+    pub fn of(elves: &[Vec<usize>]) -> Self {
+        Self::new(
+            elves
+                .iter()
+                .map(|snacks| (snacks.len(), snacks.iter().sum()))
+                .collect(),
+        )
+    }
+
+    /// From each elf's snack count and calories, as
+    /// [`stream::stats`](super::stream::stats) reads them.
+    pub fn new(elves: Vec<(usize, usize)>) -> Self {
+        let mut sorted = elves.iter().map(|&(_, calories)| calories).collect::<Vec<_>>();
+        sorted.sort_unstable();
+        Self { elves, sorted }
+    }
+
+    pub fn calories(&self) -> usize {
+        self.sorted.iter().sum()
+    }
+
+    pub fn snacks(&self) -> usize {
+        self.elves.iter().map(|&(snacks, _)| snacks).sum()
+    }
+
+    /// `None` without any elves, as for the other averages.
+    pub fn mean(&self) -> Option<f64> {
+        (!self.sorted.is_empty()).then(|| self.calories() as f64 / self.sorted.len() as f64)
+    }
+
+    /// The middle elf's calories, or the mean of the middle two.
+    pub fn median(&self) -> Option<f64> {
+        let n = self.sorted.len();
+        match n {
+            0 => None,
+            _ if n % 2 == 1 => Some(self.sorted[n / 2] as f64),
+            _ => Some((self.sorted[n / 2 - 1] as f64 + self.sorted[n / 2] as f64) / 2.0),
+        }
+    }
+
+    /// The fewest calories that at least `p` percent of the elves carry no
+    /// more than: the nearest-rank percentile.
+    pub fn percentile(&self, p: u8) -> Option<usize> {
+        let rank = (p as usize * self.sorted.len()).div_ceil(100).max(1);
+        self.sorted.get(rank - 1).copied()
+    }
+
+    /// How many elves carry calories within each of `buckets` equally wide
+    /// ranges (at most [`MAX_BUCKETS`]), from the least any elf carries to
+    /// the most, with bars of `#` up to `width` long. The ranges are worked out in `u128`, as the widest
+    /// spans all of `usize`.
+    pub fn histogram(&self, buckets: usize, width: usize) -> String {
+        let (Some(&min), Some(&max)) = (self.sorted.first(), self.sorted.last()) else {
+            return String::new();
+        };
+        let min = min as u128;
+        let span = max as u128 - min + 1;
+        let size = span.div_ceil(buckets.clamp(1, MAX_BUCKETS) as u128);
+        let mut counts = vec![0; ((span - 1) / size + 1) as usize];
+        for &calories in &self.sorted {
+            counts[((calories as u128 - min) / size) as usize] += 1;
+        }
+
+        let most = *counts.iter().max().unwrap();
+        let digits = (min + counts.len() as u128 * size - 1).to_string().len();
+        counts
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| {
+                let start = min + i as u128 * size;
+                format!(
+                    "{:>digits$}-{:>digits$} {:<width$} {}",
+                    start,
+                    start + size - 1,
+                    "#".repeat((count * width).div_ceil(most)),
+                    count
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Totals, averages, percentiles and a histogram of the calories.
+    pub fn report(&self, buckets: usize) -> String {
+        let (Some(mean), Some(median)) = (self.mean(), self.median()) else {
+            return "no elves".to_string();
+        };
+        let percentiles = [10, 25, 75, 90, 99]
+            .map(|p| format!("p{} {}", p, self.percentile(p).unwrap()))
+            .join(", ");
+        format!(
+            "elves: {}\nsnacks: {} ({:.1} per elf)\ncalories: {}\n\
+             mean: {:.1}\nmedian: {:.1}\npercentiles: {}\n{}",
+            self.elves.len(),
+            self.snacks(),
+            self.snacks() as f64 / self.elves.len() as f64,
+            self.calories(),
+            mean,
+            median,
+            percentiles,
+            self.histogram(buckets, 40)
+        )
+    }
+}
@@ -0,0 +1,245 @@
+//! Day 1 over inventories too large to read into memory, such as generated
+//! ones of many gigabytes: the lines are read one at a time, and only the
+//! three elves carrying the most are kept, or for [`stats`] each elf's
+//! totals.
+
+use std::{io::BufRead, mem};
+
+use super::{stats::Stats, Elf, Entry, Inventory, Mode, Ranking, Reader};
+use crate::{
+    error::{Line, ParseError, StreamError},
+    trace,
+};
+
+/// Lines read between progress reports, which are traced at the `Steps`
+/// level.
+pub const PROGRESS_EVERY: usize = 1 << 20;
+
+/// The inventory as one [`Mode`] reads it, keeping the best three elves.
+struct Reading {
+    reader: Reader,
+    ranking: Ranking,
+    elf: Elf,
+}
+
+impl Reading {
+    fn new(mode: Mode) -> Self {
+        Self {
+            reader: Reader::new(mode),
+            ranking: Ranking::new(3),
+            elf: Elf {
+                number: 1,
+                calories: 0,
+            },
+        }
+    }
+
+    fn read(&mut self, line: &Line) {
+        match self.reader.read(line) {
+            // The reader keeps the total of all snacks within a `usize`, in
+            // either mode, so no elf's can overflow.
+            Entry::Snack(calories) => self.elf.calories += calories,
+            Entry::NextElf => {
+                self.ranking.push(self.elf);
+                self.elf = Elf {
+                    number: self.elf.number + 1,
+                    calories: 0,
+                };
+            }
+            Entry::Skip => {}
+        }
+    }
+
+    /// The best three elves, each holding their calories as one snack, or
+    /// every problem the reading found.
+    fn finish(mut self) -> Result<Vec<Vec<usize>>, Vec<ParseError>> {
+        if self.reader.finish(self.elf.number - 1)? {
+            self.ranking.push(self.elf);
+        }
+        let top = self.ranking.into_top();
+        Ok(top.elves.iter().map(|elf| vec![elf.calories]).collect())
+    }
+}
+
+/// Parses `input` as [`Inventory::parse`] does, holding only the line being
+/// read and the best three elves of each reading, which is all both parts
+/// need. The strict reading notes every problem, and the lenient one goes on
+/// in case the parts ask for it; it stops at its first, as parsing does.
+pub fn parse(input: impl BufRead) -> Result<Inventory, StreamError> {
+    let mut strict = Reading::new(Mode::Strict);
+    let mut lenient = Reading::new(Mode::Lenient);
+    read_lines(input, |line| {
+        strict.read(line);
+        lenient.read(line);
+        if let Some(problem) = lenient.reader.problems.first() {
+            return Err(problem.clone().into());
+        }
+        if line.number % PROGRESS_EVERY == 0 {
+            trace!(Steps, "line {}: {} elves", line.number, lenient.elf.number - 1);
+        }
+        Ok(())
+    })?;
+
+    let lenient = lenient.finish().map_err(|mut e| e.remove(0))?;
+    Ok(match strict.finish() {
+        Ok(elves) => Inventory {
+            elves,
+            problems: vec![],
+        },
+        Err(problems) => Inventory {
+            elves: lenient,
+            problems,
+        },
+    })
+}
+
+/// The statistics of `input` as `mode` reads it, holding each elf's snack
+/// count and calories but not the snacks. Fails with every problem found.
+pub fn stats(input: impl BufRead, mode: Mode) -> Result<Stats, StreamError> {
+    let mut reader = Reader::new(mode);
+    let mut elves = vec![];
+    let (mut snacks, mut calories) = (0, 0);
+    read_lines(input, |line| {
+        match reader.read(line) {
+            Entry::Snack(snack) => (snacks, calories) = (snacks + 1, calories + snack),
+            Entry::NextElf => elves.push((mem::take(&mut snacks), mem::take(&mut calories))),
+            Entry::Skip => {}
+        }
+        Ok(())
+    })?;
+    if reader.finish(elves.len()).map_err(StreamError::Problems)? {
+        elves.push((snacks, calories));
+    }
+    Ok(Stats::new(elves))
+}
+
+/// Hands each line of `input` to `each` until the end or its first error,
+/// with line endings taken as `str::lines` takes them: `\n` or `\r\n`.
+fn read_lines(
+    mut input: impl BufRead,
+    mut each: impl FnMut(&Line) -> Result<(), StreamError>,
+) -> Result<(), StreamError> {
+    let mut text = String::new();
+    for number in 1.. {
+        text.clear();
+        if input.read_line(&mut text)? == 0 {
+            break;
+        }
+        let text = match text.strip_suffix('\n') {
+            Some(text) => text.strip_suffix('\r').unwrap_or(text),
+            None => &text,
+        };
+        each(&Line { number, text })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        days::day01::{generate_input, Solver},
+        error::SolveError,
+        generate::Rng,
+        params::Params,
+        solution::{Answer, Solution},
+        trace::{self, Level},
+    };
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    /// Both parts' answers from an inventory, as `params` ask.
+    fn answers(inventory: &Inventory, params: &Params) -> Result<[Answer; 2], SolveError> {
+        Ok([
+            Solver::part1(inventory, params)?,
+            Solver::part2(inventory, params)?.unwrap(),
+        ])
+    }
+
+    fn lenient() -> Params {
+        let mut params = Params::default();
+        params.set(Solver::PARAMS, "lenient", "1").unwrap();
+        params
+    }
+
+    #[test]
+    fn test_parse() {
+        let inventory = parse(INPUT.as_bytes()).unwrap();
+        let expected = [Answer::Integer(24000), Answer::Integer(45000)];
+        assert_eq!(answers(&inventory, &Params::default()), Ok(expected.clone()));
+        assert_eq!(answers(&inventory, &lenient()), Ok(expected));
+        let crlf = INPUT.replace('\n', "\r\n");
+        let inventory = parse(crlf.as_bytes()).unwrap();
+        assert_eq!(Solver::part2(&inventory, &Params::default()), Ok(Some(Answer::Integer(45000))));
+
+        for seed in 0..10 {
+            let input = generate_input(&mut Rng::new(seed), 20);
+            let loaded = Solver::parse(&input).unwrap();
+            let streamed = parse(input.as_bytes()).unwrap();
+            assert_eq!(streamed.elves.len(), 3);
+            assert_eq!(
+                answers(&streamed, &Params::default()),
+                answers(&loaded, &Params::default())
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = "1000\n\n12a4\n3000\n\n\n5\n";
+        let inventory = parse(input.as_bytes()).unwrap();
+        assert_eq!(
+            answers(&inventory, &Params::default()),
+            Err(SolveError::Problems(vec![
+                ParseError::new(3, 1, "a calorie count or an empty line", "`12a4`"),
+                ParseError::new(6, 1, "a calorie count", "end of line"),
+            ]))
+        );
+        let loaded = Solver::parse(input).unwrap();
+        assert_eq!(answers(&inventory, &lenient()), answers(&loaded, &lenient()));
+
+        let overflow = format!("{}\nx\n1\n", usize::MAX);
+        match parse(overflow.as_bytes()) {
+            Err(StreamError::Parse(e)) => assert_eq!(
+                (e.line, e.expected.as_str()),
+                (3, "calories that keep the total within a `usize`")
+            ),
+            _ => panic!("expected a parse error"),
+        }
+        let error = parse(&[b'1', b'\n', 0xff, b'\n'][..]).unwrap_err();
+        assert!(matches!(error, StreamError::Read(_)));
+    }
+
+    #[test]
+    fn test_stats() {
+        let loaded = Stats::of(&Solver::parse(INPUT).unwrap().elves);
+        let streamed = stats(INPUT.as_bytes(), Mode::Strict).unwrap();
+        assert_eq!(streamed.elves, loaded.elves);
+        assert_eq!(streamed.report(3), loaded.report(3));
+
+        let input = "1000\n12a4\n3000\n\n5x00\n";
+        match stats(input.as_bytes(), Mode::Strict) {
+            Err(StreamError::Problems(problems)) => {
+                assert_eq!(problems.iter().map(|e| e.line).collect::<Vec<_>>(), [2, 5])
+            }
+            _ => panic!("expected every problem"),
+        }
+        let lenient = stats(input.as_bytes(), Mode::Lenient).unwrap();
+        assert_eq!(lenient.elves, [(1, 1000), (1, 3000), (0, 0), (0, 0)]);
+
+        let mut params = Params::default();
+        params.set(Solver::PARAMS, "buckets", "2").unwrap();
+        let report = Solver::stats(&mut INPUT.as_bytes(), &params).unwrap().unwrap();
+        assert!(report.ends_with("\n 4000-14000 ######################################## 4\n\
+                                  14001-24001 ##########                               1"));
+    }
+
+    #[test]
+    fn test_progress() {
+        let input = "1\n".repeat(PROGRESS_EVERY) + "\n2\n";
+        let (inventory, traced) = trace::capture(Level::Steps, || parse(input.as_bytes()));
+        let part2 = Solver::part2(&inventory.unwrap(), &Params::default());
+        assert_eq!(part2, Ok(Some(Answer::Integer(PROGRESS_EVERY as i128 + 2))));
+        assert_eq!(traced, format!("line {}: 0 elves\n", PROGRESS_EVERY));
+    }
+}
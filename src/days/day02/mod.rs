@@ -0,0 +1,314 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+};
+
+/// A column of the strategy guide holding something other than one of the
+/// letters it takes.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LetterError {
+    /// The letters the column takes, like `ABC`.
+    pub expected: &'static str,
+    pub found: String,
+}
+
+impl LetterError {
+    fn new(expected: &'static str, found: &str) -> Self {
+        Self {
+            expected,
+            found: found.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for LetterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected one of {}, found `{}`", self.expected, self.found)
+    }
+}
+
+impl std::error::Error for LetterError {}
+
+/// A hand in rock paper scissors.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    pub const ALL: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissors];
+
+    /// The shape this one defeats.
+    pub fn beats(self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Scissors,
+            Shape::Paper => Shape::Rock,
+            Shape::Scissors => Shape::Paper,
+        }
+    }
+
+    /// The response's letters as part 1 guesses them: `X`, `Y` or `Z`.
+    pub fn from_response(s: &str) -> Result<Self, LetterError> {
+        match s {
+            "X" => Ok(Shape::Rock),
+            "Y" => Ok(Shape::Paper),
+            "Z" => Ok(Shape::Scissors),
+            _ => Err(LetterError::new("XYZ", s)),
+        }
+    }
+
+    fn score(self) -> i64 {
+        match self {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissors => 3,
+        }
+    }
+}
+
+/// The opponent's letters, `A`, `B` or `C`; the response's are read by
+/// [`Shape::from_response`].
+impl FromStr for Shape {
+    type Err = LetterError;
+
+    fn from_str(s: &str) -> Result<Self, LetterError> {
+        match s {
+            "A" => Ok(Shape::Rock),
+            "B" => Ok(Shape::Paper),
+            "C" => Ok(Shape::Scissors),
+            _ => Err(LetterError::new("ABC", s)),
+        }
+    }
+}
+
+/// How a round ends for the one responding.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    /// How playing `response` against `opponent` ends.
+    pub fn of(opponent: Shape, response: Shape) -> Outcome {
+        match winner(opponent, response) {
+            None => Outcome::Draw,
+            Some(shape) if shape == response => Outcome::Win,
+            Some(_) => Outcome::Loss,
+        }
+    }
+
+    fn score(self) -> i64 {
+        match self {
+            Outcome::Loss => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
+        }
+    }
+}
+
+/// The response's letters as part 2 reads them: `X`, `Y` or `Z`.
+impl FromStr for Outcome {
+    type Err = LetterError;
+
+    fn from_str(s: &str) -> Result<Self, LetterError> {
+        match s {
+            "X" => Ok(Outcome::Loss),
+            "Y" => Ok(Outcome::Draw),
+            "Z" => Ok(Outcome::Win),
+            _ => Err(LetterError::new("XYZ", s)),
+        }
+    }
+}
+
+/// The shape that wins between `a` and `b`, or `None` for a draw.
+pub fn winner(a: Shape, b: Shape) -> Option<Shape> {
+    if a.beats() == b {
+        Some(a)
+    } else if b.beats() == a {
+        Some(b)
+    } else {
+        None
+    }
+}
+
+/// The shape to play against `opponent` for the round to end in `outcome`.
+pub fn needed(opponent: Shape, outcome: Outcome) -> Shape {
+    match outcome {
+        Outcome::Loss => opponent.beats(),
+        Outcome::Draw => opponent,
+        Outcome::Win => opponent.beats().beats(),
+    }
+}
+
+/// A round of the strategy guide: the opponent's shape, and the second
+/// column read both as a shape, for part 1, and as an outcome, for part 2.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Round {
+    pub opponent: Shape,
+    pub response: Shape,
+    pub outcome: Outcome,
+}
+
+fn round_score(round: &Round) -> i64 {
+    round.response.score() + Outcome::of(round.opponent, round.response).score()
+}
+
+fn round_score2(round: &Round) -> i64 {
+    needed(round.opponent, round.outcome).score() + round.outcome.score()
+}
+
+/// Reads the letter `at` with `read`, reporting the letters it takes if
+/// `at` isn't one of them.
+fn parse_letter<T>(
+    line: &Line,
+    at: &str,
+    read: impl Fn(&str) -> Result<T, LetterError>,
+) -> Result<T, ParseError> {
+    read(at).map_err(|e| line.error(at, &format!("one of {}", e.expected)))
+}
+
+fn parse_round(line: &Line) -> Result<Round, ParseError> {
+    match line.text.split(' ').collect::<Vec<&str>>()[..] {
+        [a, b] => Ok(Round {
+            opponent: parse_letter(line, a, Shape::from_str)?,
+            response: parse_letter(line, b, Shape::from_response)?,
+            outcome: parse_letter(line, b, Outcome::from_str)?,
+        }),
+        _ => Err(line.mismatch("a round like `A Y`")),
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Round>, ParseError> {
+    lines(input)
+        .filter(|line| !line.text.is_empty())
+        .map(|line| parse_round(&line))
+        .collect()
+}
+
+fn process(score: fn(&Round) -> i64, rounds: &[Round]) -> i64 {
+    let mut agg = 0;
+    for round in rounds {
+        agg += score(round);
+    }
+
+    agg
+}
+
+fn process_part1(rounds: &[Round]) -> i64 {
+    process(round_score, rounds)
+}
+
+fn process_part2(rounds: &[Round]) -> i64 {
+    process(round_score2, rounds)
+}
+
+/// `size` rounds.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.pick(&['A', 'B', 'C']),
+                rng.pick(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 2;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a round of the strategy guide",
+        allowed: "`A`, `B`, `C`, `X`, `Y`, `Z` or a space",
+        chars: |c| "ABC XYZ".contains(c),
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<Round>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(rounds: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(rounds).into())
+    }
+
+    fn part2(rounds: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(rounds).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 15);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 12);
+    }
+
+    #[test]
+    fn test_shapes() {
+        use Shape::*;
+        assert_eq!(winner(Rock, Scissors), Some(Rock));
+        assert_eq!(winner(Rock, Paper), Some(Paper));
+        assert_eq!(winner(Scissors, Scissors), None);
+        for opponent in Shape::ALL {
+            for outcome in [Outcome::Loss, Outcome::Draw, Outcome::Win] {
+                assert_eq!(Outcome::of(opponent, needed(opponent, outcome)), outcome);
+            }
+        }
+        assert_eq!("C".parse(), Ok(Scissors));
+        assert_eq!(Shape::from_response("Z"), Ok(Scissors));
+        assert_eq!("Z".parse::<Outcome>(), Ok(Outcome::Win));
+        // Each column's letters are only read as that column's.
+        assert_eq!("X".parse::<Shape>(), Err(LetterError::new("ABC", "X")));
+        assert_eq!(Shape::from_response("A"), Err(LetterError::new("XYZ", "A")));
+        let error = "A".parse::<Outcome>().unwrap_err();
+        assert_eq!(error.to_string(), "expected one of XYZ, found `A`");
+    }
+
+    #[test]
+    fn test_large_input() {
+        let input = "A Y\nB X\nC Z\n".repeat(100_000);
+        let rounds = parse_input(&input).unwrap();
+        assert_eq!(process_part1(&rounds), 1_500_000);
+        assert_eq!(process_part2(&rounds), 1_200_000);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("A Y\nB W").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "one of XYZ");
+        let error = parse_input("X Y").unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (1, "one of ABC"));
+        let error = parse_input("A AB").unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (3, "`AB`"));
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashSet;
+
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+};
+
+fn type_priority(c: &char) -> i64 {
+    match c {
+        'a'..='z' => *c as i64 - 'a' as i64 + 1,
+        'A'..='Z' => *c as i64 - 'A' as i64 + 27,
+        _ => 0,
+    }
+}
+
+fn rucksack_priority(items: &[char]) -> i64 {
+    let mut compartments = items
+        .chunks(items.len() / 2)
+        .map(|c| HashSet::<_>::from_iter(c.to_vec()));
+    compartments
+        .next()
+        .expect("Failed to get left compartment")
+        .intersection(
+            &compartments
+                .next()
+                .expect("Failed to get right compartment"),
+        )
+        .map(type_priority)
+        .sum()
+}
+
+/// A rucksack's items, which must be letters, and as many in each of its two
+/// compartments.
+fn parse_rucksack(line: &Line) -> Result<Vec<char>, ParseError> {
+    let item = line
+        .text
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii_alphabetic());
+    if let Some((i, c)) = item {
+        return Err(line.error(&line.text[i..i + c.len_utf8()], "an item type, a letter"));
+    }
+    if line.text.is_empty() || line.text.len() % 2 == 1 {
+        return Err(line.mismatch("a rucksack with an even number of items, at least 2"));
+    }
+    Ok(line.text.chars().collect())
+}
+
+fn parse_input(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    lines(input).map(|line| parse_rucksack(&line)).collect()
+}
+
+fn process_part1(rucksacks: &[Vec<char>]) -> i64 {
+    rucksacks.iter().map(|r| rucksack_priority(r)).sum()
+}
+
+fn process_part2(rucksacks: &[Vec<char>]) -> i64 {
+    let mut agg = 0;
+    for group in rucksacks.chunks(3) {
+        agg += group[1..]
+            .iter()
+            .map(|a| HashSet::from_iter(a.iter().copied()))
+            .fold(group[0].clone(), |a, b| {
+                HashSet::<_>::from_iter(a)
+                    .intersection(&b)
+                    .copied()
+                    .collect::<Vec<char>>()
+            })
+            .iter()
+            .map(type_priority)
+            .sum::<i64>();
+    }
+
+    agg
+}
+
+const ITEM_TYPES: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// `half` items drawn from `types`, including each of `required` at least once.
+fn compartment(rng: &mut Rng, types: &[char], required: &[char], half: usize) -> Vec<char> {
+    let mut items = required.to_vec();
+    while items.len() < half {
+        items.push(*rng.pick(types));
+    }
+    rng.shuffle(&mut items);
+    items
+}
+
+/// One rucksack of the group with `badge`, made of item types from `pool`,
+/// which no other rucksack of the group uses.
+fn rucksack(rng: &mut Rng, mut pool: Vec<char>, badge: char) -> String {
+    let shared = if rng.chance(1, 4) {
+        badge
+    } else {
+        pool.pop().unwrap()
+    };
+    let (left, right) = pool.split_at(pool.len() / 2);
+    let (mut left, mut right) = ([left, &[shared]].concat(), [right, &[shared]].concat());
+    let (mut left_required, mut right_required) = (vec![shared], vec![shared]);
+    if shared != badge {
+        if rng.chance(1, 2) {
+            left.push(badge);
+            left_required.push(badge);
+        } else {
+            right.push(badge);
+            right_required.push(badge);
+        }
+    }
+
+    let half = rng.range(3..=16) as usize;
+    compartment(rng, &left, &left_required, half)
+        .into_iter()
+        .chain(compartment(rng, &right, &right_required, half))
+        .collect()
+}
+
+/// `size` rucksacks, rounded up to whole groups of three.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut result = String::new();
+    for _ in 0..size.div_ceil(3) {
+        let mut types = ITEM_TYPES.chars().collect::<Vec<_>>();
+        rng.shuffle(&mut types);
+        let badge = types.pop().unwrap();
+        for pool in types.chunks(types.len() / 3) {
+            result += &rucksack(rng, pool.to_vec(), badge);
+            result += "\n";
+        }
+    }
+    result
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 3;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a rucksack",
+        allowed: "a letter",
+        chars: |c| c.is_ascii_alphabetic(),
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(rucksacks: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(rucksacks).into())
+    }
+
+    fn part2(rucksacks: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(rucksacks).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 157);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 70);
+    }
+
+    #[test]
+    fn test_large_input() {
+        let input = format!("{}\n", INPUT.trim_end()).repeat(10_000);
+        let rucksacks = parse_input(&input).unwrap();
+        assert_eq!(process_part1(&rucksacks), 157 * 10_000);
+        assert_eq!(process_part2(&rucksacks), 70 * 10_000);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = |input| parse_input(input).err().map(|e| (e.line, e.column));
+        assert_eq!(error("abcb\nx"), Some((2, 1)));
+        assert_eq!(error("abA"), Some((1, 1)));
+        assert_eq!(error("ab1b"), Some((1, 3)));
+        assert_eq!(error("aa\n\nbb"), Some((2, 1)));
+        let odd = parse_input("abA").err().unwrap();
+        assert_eq!(
+            odd.expected,
+            "a rucksack with an even number of items, at least 2"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+};
+
+type Range = (u8, u8);
+
+fn parse_range(line: &Line, value: &str) -> Result<Range, ParseError> {
+    match value.split('-').collect::<Vec<&str>>()[..] {
+        [a, b] => Ok((
+            line.parse(a, "a section number")?,
+            line.parse(b, "a section number")?,
+        )),
+        _ => Err(line.error(value, "a range like `2-4`")),
+    }
+}
+
+fn parse_line(line: Line) -> Result<(Range, Range), ParseError> {
+    match line.text.split(',').collect::<Vec<&str>>()[..] {
+        [a, b] => Ok((parse_range(&line, a)?, parse_range(&line, b)?)),
+        _ => Err(line.mismatch("two ranges separated by `,`")),
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<(Range, Range)>, ParseError> {
+    lines(input).map(parse_line).collect()
+}
+
+fn contains<T: PartialOrd>(a: &(T, T), b: &(T, T)) -> bool {
+    a.0 >= b.0 && a.1 <= b.1
+}
+
+fn overlap<T: PartialOrd>(a: &(T, T), b: &(T, T)) -> bool {
+    a.0 <= b.1 && a.1 >= b.0
+}
+
+fn either<T: PartialOrd, F: Fn(&(T, T), &(T, T)) -> bool>(f: F, a: &(T, T), b: &(T, T)) -> bool {
+    f(a, b) || f(b, a)
+}
+
+fn process_part1(pairs: &[(Range, Range)]) -> usize {
+    pairs
+        .iter()
+        .filter(|(a, b)| either(contains, a, b))
+        .count()
+}
+
+fn process_part2(pairs: &[(Range, Range)]) -> usize {
+    pairs
+        .iter()
+        .filter(|(a, b)| either(overlap, a, b))
+        .count()
+}
+
+/// `size` pairs of elves.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut range = || {
+        let (a, b) = (rng.range(1..=99), rng.range(1..=99));
+        format!("{}-{}", a.min(b), a.max(b))
+    };
+    (0..size)
+        .map(|_| format!("{},{}\n", range(), range()))
+        .collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 4;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a pair of assignments",
+        allowed: "a digit, `-` or `,`",
+        chars: |c| c.is_ascii_digit() || c == '-' || c == ',',
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<(Range, Range)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(pairs: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(pairs).into())
+    }
+
+    fn part2(pairs: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(pairs).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 4);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("2-4,6-8\n2-3,4-x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 7));
+        assert_eq!(error.found, "`x`");
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+    trace,
+};
+
+type Instruction = (usize, usize, usize);
+
+/// The stacks, bottom to top, and the rearrangement.
+type Procedure = (Vec<Vec<char>>, Vec<Instruction>);
+
+fn parse_instruction(line: Line, stack_count: usize) -> Result<Instruction, ParseError> {
+    let parse_stack = |value| {
+        let stack = line.parse(value, "a stack number")?;
+        if (1..=stack_count).contains(&stack) {
+            Ok(stack)
+        } else {
+            Err(line.error(value, &format!("a stack between 1 and {}", stack_count)))
+        }
+    };
+
+    match line.text.split_whitespace().collect::<Vec<&str>>()[..] {
+        ["move", count, "from", from, "to", to] => Ok((
+            line.parse(count, "a crate count")?,
+            parse_stack(from)?,
+            parse_stack(to)?,
+        )),
+        _ => Err(line.mismatch("an instruction like `move 1 from 2 to 3`")),
+    }
+}
+
+fn parse_input(input: &str) -> Result<Procedure, ParseError> {
+    let mut iter = lines(input);
+    let field_lines = iter
+        .by_ref()
+        .take_while(|line| !line.text.is_empty())
+        .collect::<Vec<Line>>();
+    let mut field_lines_iter = field_lines.iter().rev();
+
+    let stack_numbers = field_lines_iter
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(0, "a drawing of the stacks"))?;
+    let stack_count = (stack_numbers.text.len() + 1) / 4;
+    let mut field = vec![vec![]; stack_count];
+    for line in field_lines_iter {
+        for (idx, value) in line.text.char_indices().filter(|(_, c)| c.is_alphabetic()) {
+            match field.get_mut(idx / 4) {
+                Some(stack) => stack.push(value),
+                None => return Err(line.error(&line.text[idx..], "a crate above a stack number")),
+            }
+        }
+    }
+
+    // Both parts move as many crates off each stack, so a step taking more
+    // than a stack holds shows in the heights alone.
+    let mut heights = field.iter().map(Vec::len).collect::<Vec<_>>();
+    let mut instructions = vec![];
+    for line in iter.filter(|line| !line.text.is_empty()) {
+        let (count, from, to) = parse_instruction(line, stack_count)?;
+        let height = heights[from - 1];
+        if count > height {
+            let at = line.text.split_whitespace().nth(1).unwrap();
+            let expected = format!("at most the {} crates stack {} holds", height, from);
+            return Err(line.error(at, &expected));
+        }
+        heights[from - 1] -= count;
+        heights[to - 1] += count;
+        instructions.push((count, from, to));
+    }
+
+    Ok((field, instructions))
+}
+
+/// Draws the stacks bottom to top, one per line.
+fn format(field: &[Vec<char>]) -> String {
+    field
+        .iter()
+        .enumerate()
+        .map(|(i, stack)| {
+            let crates = stack.iter().collect::<String>();
+            format!("{} {}", i + 1, crates).trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn move_sequential(mut field: Vec<Vec<char>>, params: Instruction) -> Vec<Vec<char>> {
+    let (count, from, to) = params;
+    for _ in 0..count {
+        field[from - 1]
+            .pop()
+            .iter()
+            .copied()
+            .for_each(|c| field[to - 1].push(c));
+    }
+    field
+}
+
+fn move_batch(mut field: Vec<Vec<char>>, params: (usize, usize, usize)) -> Vec<Vec<char>> {
+    let (count, from, to) = params;
+    let split_index = field[from - 1].len() - count;
+    let mut moved = field[from - 1].split_off(split_index);
+    field[to - 1].append(&mut moved);
+    field
+}
+
+/// The crates on top of the stacks at the end, or `None` if a stack is
+/// left empty.
+fn process<F: Fn(Vec<Vec<char>>, (usize, usize, usize)) -> Vec<Vec<char>>>(
+    procedure: &Procedure,
+    move_fn: F,
+) -> Option<String> {
+    let (field, instructions) = procedure;
+    let mut field = field.clone();
+
+    for &params @ (count, from, to) in instructions {
+        field = move_fn(field, params);
+        trace!(Steps, "move {} from {} to {}", count, from, to);
+        trace!(States, "{}\n", format(&field));
+    }
+
+    field.iter().map(|stack| stack.last()).collect()
+}
+
+fn process_part1(procedure: &Procedure) -> Option<String> {
+    process(procedure, move_sequential)
+}
+
+fn process_part2(procedure: &Procedure) -> Option<String> {
+    process(procedure, move_batch)
+}
+
+/// Nine stacks and `size` moves, which never take a stack's last crate, so
+/// every stack has a crate on top at the end.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    const STACKS: usize = 9;
+
+    let mut heights = (0..STACKS)
+        .map(|_| rng.range(1..=8) as usize)
+        .collect::<Vec<_>>();
+    let tallest = *heights.iter().max().unwrap();
+
+    let mut result = String::new();
+    for level in (0..tallest).rev() {
+        let row = heights
+            .iter()
+            .map(|&h| {
+                if level < h {
+                    format!("[{}]", (b'A' + rng.below(26) as u8) as char)
+                } else {
+                    "   ".to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        result += &format!("{}\n", row.join(" "));
+    }
+    let numbers = (1..=STACKS).map(|n| format!(" {} ", n)).collect::<Vec<_>>();
+    result += &format!("{}\n\n", numbers.join(" "));
+
+    for _ in 0..size {
+        let from = loop {
+            let from = rng.below(STACKS);
+            if heights[from] > 1 || heights.iter().all(|&h| h <= 1) {
+                break from;
+            }
+        };
+        let to = (from + 1 + rng.below(STACKS - 1)) % STACKS;
+        let count = match heights[from] {
+            1 => 0,
+            height => rng.range(1..=height as i64 - 1) as usize,
+        };
+        heights[from] -= count;
+        heights[to] += count;
+        result += &format!("move {} from {} to {}\n", count, from + 1, to + 1);
+    }
+    result
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 5;
+    const SECTIONS: &'static [Section] = &[
+        Section {
+            line: "a row of crates",
+            allowed: "a crate, a stack number or a space",
+            chars: |c| c.is_ascii_uppercase() || c.is_ascii_digit() || "[] ".contains(c),
+            count: 2..=usize::MAX,
+            grid: true,
+            ..Section::TEXT
+        },
+        Section {
+            line: "a rearrangement step",
+            allowed: "a lowercase letter, a digit or a space",
+            chars: |c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == ' ',
+            ..Section::TEXT
+        },
+    ];
+
+    type Parsed = Procedure;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(procedure: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        match process_part1(procedure) {
+            Some(tops) => Ok(tops.into()),
+            None => Err(SolveError::no_answer("crate on top of every stack")),
+        }
+    }
+
+    fn part2(procedure: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        match process_part2(procedure) {
+            Some(tops) => Ok(Some(tops.into())),
+            None => Err(SolveError::no_answer("crate on top of every stack")),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, Some("CMZ".to_string()));
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(result, Some("MCD".to_string()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        let error = parse_input(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (7, 6));
+        assert_eq!(error.expected, "at most the 3 crates stack 1 holds");
+
+        let emptied = parse_input(&INPUT.replace("move 1 from 1 to 2", "move 0 from 1 to 2"));
+        assert_eq!(process_part1(&emptied.unwrap()), None);
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+
+use crate::{
+    error::{ParseError, SolveError},
+    generate::{Rng, LOWERCASE},
+    lint::Section,
+    params::{Param, Params},
+    solution::{Answer, Solution},
+};
+
+fn is_distinct(input: &[char]) -> bool {
+    HashSet::<_>::from_iter(input).len() == input.len()
+}
+
+fn parse_input(input: &str) -> Result<Vec<char>, ParseError> {
+    match input.lines().next() {
+        Some(line) => Ok(line.chars().collect()),
+        None => Err(ParseError::end_of_input(0, "the datastream")),
+    }
+}
+
+/// Characters read up to the end of the first `size` distinct ones, or
+/// `None` if there are never that many in a row.
+fn process(stream: &[char], size: usize) -> Option<usize> {
+    Some(stream.windows(size).position(is_distinct)? + size)
+}
+
+const PART1_WINDOW: Param = Param {
+    name: "part1-window",
+    default: 4,
+    range: 1..=26,
+    help: "distinct characters that start a packet",
+};
+
+const PART2_WINDOW: Param = Param {
+    name: "part2-window",
+    default: 14,
+    range: 1..=26,
+    help: "distinct characters that start a message",
+};
+
+/// A datastream of `size` characters, at least 14, with a start-of-message
+/// marker somewhere in it.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let len = size.max(14);
+    let mut stream = (0..len).map(|_| *rng.pick(&LOWERCASE)).collect::<Vec<_>>();
+
+    let mut marker = LOWERCASE.to_vec();
+    rng.shuffle(&mut marker);
+    let at = rng.below(len - 13);
+    stream[at..at + 14].copy_from_slice(&marker[..14]);
+
+    format!("{}\n", stream.iter().collect::<String>())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 6;
+    const PARAMS: &'static [Param] = &[PART1_WINDOW, PART2_WINDOW];
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "the datastream",
+        allowed: "a lowercase letter",
+        chars: |c| c.is_ascii_lowercase(),
+        count: 1..=1,
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<char>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(stream: &Self::Parsed, params: &Params) -> Result<Answer, SolveError> {
+        let window = params.get(&PART1_WINDOW);
+        match process(stream, window) {
+            Some(count) => Ok(count.into()),
+            None => Err(SolveError::no_answer("start-of-packet marker")),
+        }
+    }
+
+    fn part2(stream: &Self::Parsed, params: &Params) -> Result<Option<Answer>, SolveError> {
+        let window = params.get(&PART2_WINDOW);
+        match process(stream, window) {
+            Some(count) => Ok(Some(count.into())),
+            None => Err(SolveError::no_answer("start-of-message marker")),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(stream: &str, size: usize) -> usize {
+        process(&parse_input(stream).unwrap(), size).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4), 7);
+        assert_eq!(marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), 5);
+        assert_eq!(marker("nppdvjthqldpwncqszvftbrmjlhg", 4), 6);
+        assert_eq!(marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4), 10);
+        assert_eq!(marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4), 11);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14), 19);
+        assert_eq!(marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 14), 23);
+        assert_eq!(marker("nppdvjthqldpwncqszvftbrmjlhg", 14), 23);
+        assert_eq!(marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14), 29);
+        assert_eq!(marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14), 26);
+    }
+
+    #[test]
+    fn test_window_bounds() {
+        let stream = parse_input("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
+        assert_eq!(process(&stream, 1), Some(1));
+        assert_eq!(process(&stream, 26), None);
+        let mut params = Params::default();
+        params.set(Solver::PARAMS, "part1-window", "26").unwrap();
+        assert_eq!(
+            Solver::part1(&stream, &params),
+            Err(SolveError::no_answer("start-of-packet marker"))
+        );
+    }
+}
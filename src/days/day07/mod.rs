@@ -0,0 +1,412 @@
+use std::iter::{once, Iterator};
+
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::{self, Rng},
+    lint::Section,
+    params::Params,
+    repl::{Model, Query},
+    solution::{Answer, Solution},
+};
+
+/// The root's index in a [`Tree`].
+const ROOT: usize = 0;
+
+#[derive(Clone, Debug)]
+struct Dir {
+    name: String,
+    own_size: usize,
+    parent: Option<usize>,
+    /// Indices into the [`Tree`].
+    subdirs: Vec<usize>,
+}
+
+/// Every directory, by index: the root first, and each after its parent.
+#[derive(Clone, Debug)]
+pub struct Tree {
+    dirs: Vec<Dir>,
+}
+
+impl Tree {
+    fn new() -> Tree {
+        Tree {
+            dirs: vec![Dir {
+                name: "/".to_string(),
+                own_size: 0,
+                parent: None,
+                subdirs: vec![],
+            }],
+        }
+    }
+
+    fn subdir(&self, dir: usize, name: &str) -> Option<usize> {
+        self.dirs[dir]
+            .subdirs
+            .iter()
+            .copied()
+            .find(|&d| self.dirs[d].name == name)
+    }
+
+    fn ensure(&mut self, dir: usize, subdir_name: String) {
+        if self.subdir(dir, &subdir_name).is_some() {
+            return;
+        }
+
+        self.dirs.push(Dir {
+            name: subdir_name,
+            own_size: 0,
+            parent: Some(dir),
+            subdirs: vec![],
+        });
+        let subdir = self.dirs.len() - 1;
+        self.dirs[dir].subdirs.push(subdir);
+    }
+
+    fn get(&self, path: &[&str]) -> Option<usize> {
+        path.iter()
+            .try_fold(ROOT, |dir, name| self.subdir(dir, name))
+    }
+
+    fn total_size(&self, dir: usize) -> usize {
+        let mut total = 0;
+        let mut stack = vec![dir];
+        while let Some(dir) = stack.pop() {
+            total += self.dirs[dir].own_size;
+            stack.extend(&self.dirs[dir].subdirs);
+        }
+        total
+    }
+
+    /// Every directory's total size, by index. Children come after their
+    /// parents, so going backwards adds each total to its parent's in time.
+    fn total_sizes(&self) -> Vec<usize> {
+        let mut totals = self.dirs.iter().map(|d| d.own_size).collect::<Vec<_>>();
+        for (i, dir) in self.dirs.iter().enumerate().rev() {
+            if let Some(parent) = dir.parent {
+                totals[parent] += totals[i];
+            }
+        }
+        totals
+    }
+}
+
+/// Adds a listed file or directory to `dir`. `used` is the size of every file
+/// so far; while it fits in a `usize`, so does every directory's total size.
+fn populate(tree: &mut Tree, dir: usize, line: &Line, used: &mut usize) -> Result<(), ParseError> {
+    match line.text.split_whitespace().collect::<Vec<&str>>()[..] {
+        ["dir", subdir_name] => {
+            tree.ensure(dir, subdir_name.to_string());
+        }
+        [file_size, _] => {
+            let size = line.parse::<usize>(file_size, "a file size or `dir`")?;
+            *used = used.checked_add(size).ok_or_else(|| {
+                line.error(file_size, "a file size keeping the disk's total within a `usize`")
+            })?;
+            tree.dirs[dir].own_size += size;
+        }
+        _ => return Err(line.mismatch("a command, a file or a directory")),
+    };
+    Ok(())
+}
+
+fn parse_input(input: &str) -> Result<Tree, ParseError> {
+    let mut tree = Tree::new();
+    // A transcript starts in the root, whether or not it opens with `$ cd /`.
+    let mut current = ROOT;
+    let mut used = 0;
+    for line in lines(input) {
+        current = match line.text.split_whitespace().collect::<Vec<&str>>()[..] {
+            ["$", "cd", "/"] => ROOT,
+            ["$", "cd", ".."] => tree.dirs[current]
+                .parent
+                .ok_or_else(|| line.mismatch("a directory to leave, not the root"))?,
+            ["$", "cd", cd_path] => tree
+                .subdir(current, cd_path)
+                .ok_or_else(|| line.error(cd_path, "a directory listed by `ls`"))?,
+            ["$", "ls"] => current,
+            _ => {
+                populate(&mut tree, current, &line, &mut used)?;
+                current
+            }
+        };
+    }
+
+    Ok(tree)
+}
+
+fn process_part1(tree: &Tree) -> usize {
+    tree.total_sizes().into_iter().filter(|s| *s <= 100000).sum()
+}
+
+/// The size of the smallest directory whose deletion frees enough space, or
+/// 0 if there is enough already.
+fn process_part2(tree: &Tree) -> usize {
+    const TOTAL_SPACE: usize = 70000000;
+    const REQUIRED_SPACE: usize = 30000000;
+
+    let sizes = tree.total_sizes();
+    let used_space = sizes[ROOT];
+    let to_free = REQUIRED_SPACE.saturating_sub(TOTAL_SPACE.saturating_sub(used_space));
+    if to_free == 0 {
+        return 0;
+    }
+
+    // The root always frees enough, as it holds everything used.
+    sizes
+        .into_iter()
+        .filter(|s| *s >= to_free)
+        .fold(used_space, usize::min)
+}
+
+/// The directory tree, for `aoc repl`.
+struct Explorer {
+    tree: Tree,
+}
+
+impl Explorer {
+    fn dir(&self, path: &str) -> Result<usize, String> {
+        let names = path.split('/').filter(|s| !s.is_empty()).collect::<Vec<_>>();
+        self.tree
+            .get(&names)
+            .ok_or_else(|| format!("no directory `{}`", path))
+    }
+}
+
+impl Model for Explorer {
+    fn queries(&self) -> &'static [Query] {
+        &[
+            Query {
+                name: "size",
+                args: &["PATH"],
+                help: "total size of a directory, like `/a/e`",
+            },
+            Query {
+                name: "ls",
+                args: &["PATH"],
+                help: "total sizes of a directory's subdirectories and its own files",
+            },
+        ]
+    }
+
+    fn query(&self, name: &str, args: &[&str]) -> Result<String, String> {
+        let dir = self.dir(args[0])?;
+        let dirs = &self.tree.dirs;
+        Ok(match name {
+            "size" => self.tree.total_size(dir).to_string(),
+            _ => dirs[dir]
+                .subdirs
+                .iter()
+                .map(|&d| format!("{:>10} {}/\n", self.tree.total_size(d), dirs[d].name))
+                .chain(once(format!("{:>10} in files", dirs[dir].own_size)))
+                .collect(),
+        })
+    }
+}
+
+struct GeneratedDir {
+    name: String,
+    subdirs: Vec<usize>,
+    files: Vec<(usize, String)>,
+}
+
+/// A transcript exploring `size` directories, whose files add up to between
+/// 40000000 and 70000000, so part 2 has space to free up on a full disk.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut dirs = vec![GeneratedDir {
+        name: "/".to_string(),
+        subdirs: vec![],
+        files: vec![],
+    }];
+    for i in 1..size.max(1) {
+        let parent = rng.below(i);
+        let name = loop {
+            let len = rng.range(1..=8) as usize;
+            let name = generate::name(rng, len);
+            if dirs[parent].subdirs.iter().all(|&d| dirs[d].name != name) {
+                break name;
+            }
+        };
+        dirs[parent].subdirs.push(i);
+        dirs.push(GeneratedDir {
+            name,
+            subdirs: vec![],
+            files: vec![],
+        });
+    }
+
+    let file_counts = dirs
+        .iter()
+        .map(|_| rng.range(0..=4) as usize)
+        .collect::<Vec<_>>();
+    let largest = (30_000_000 / file_counts.iter().sum::<usize>().max(1)) as i64;
+    let mut used = 0;
+    for (dir, count) in dirs.iter_mut().zip(file_counts) {
+        for i in 0..count {
+            let file_size = rng.range(1..=largest) as usize;
+            used += file_size;
+            // Numbered so names stay unique without dirs clashing with files.
+            dir.files.push((
+                file_size,
+                format!("{}{}.{}", generate::name(rng, 3), i, generate::name(rng, 3)),
+            ));
+        }
+    }
+    let total = rng.range(40_000_001..=70_000_000) as usize;
+    dirs[0].files.push((total - used, "padding".to_string()));
+
+    let mut result = String::new();
+    let mut list = |result: &mut String, dir: &GeneratedDir| {
+        let mut entries = dir
+            .subdirs
+            .iter()
+            .map(|&d| format!("dir {}", dirs[d].name))
+            .chain(
+                dir.files
+                    .iter()
+                    .map(|(size, name)| format!("{} {}", size, name)),
+            )
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut entries);
+        *result += "$ ls\n";
+        entries
+            .iter()
+            .for_each(|entry| *result += &format!("{}\n", entry));
+    };
+
+    result += "$ cd /\n";
+    list(&mut result, &dirs[0]);
+    let mut path = vec![(0, 0)];
+    while let Some(&(dir, next)) = path.last() {
+        match dirs[dir].subdirs.get(next) {
+            Some(&subdir) => {
+                path.last_mut().unwrap().1 += 1;
+                result += &format!("$ cd {}\n", dirs[subdir].name);
+                list(&mut result, &dirs[subdir]);
+                path.push((subdir, 0));
+            }
+            None => {
+                path.pop();
+                if !path.is_empty() {
+                    result += "$ cd ..\n";
+                }
+            }
+        }
+    }
+    result
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 7;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a command or its output",
+        ..Section::TEXT
+    }];
+
+    type Parsed = Tree;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(tree: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(tree).into())
+    }
+
+    fn part2(tree: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(tree).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn model(tree: &Self::Parsed, _params: &Params) -> Option<Box<dyn Model>> {
+        Some(Box::new(Explorer { tree: tree.clone() }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(process_part1(&parse_input(INPUT).unwrap()), 95437);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(process_part2(&parse_input(INPUT).unwrap()), 24933642);
+    }
+
+    #[test]
+    fn test_small_disks() {
+        let root = parse_input("$ ls\n100 a").unwrap();
+        assert_eq!((process_part1(&root), process_part2(&root)), (100, 0));
+        let root = parse_input("$ cd /\n$ ls\n100 a").unwrap();
+        assert_eq!(process_part2(&root), 0);
+        let full = parse_input("$ ls\n80000000 a\ndir b\n$ cd b\n$ ls\n1 c").unwrap();
+        assert_eq!(process_part2(&full), 80000001);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let error = parse_input("$ ls\n18446744073709551615 a\n1 b").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        let error = parse_input("$ ls\n18446744073709551615 a\ndir c\n$ cd c\n$ ls\n1 b");
+        assert_eq!(error.unwrap_err().line, 6);
+    }
+
+    #[test]
+    fn test_queries() {
+        let explorer = Explorer {
+            tree: parse_input(INPUT).unwrap(),
+        };
+        assert_eq!(explorer.query("size", &["/a/e"]), Ok("584".to_string()));
+        assert_eq!(
+            explorer.query("ls", &["/"]),
+            Ok("     94853 a/\n  24933642 d/\n  23352670 in files".to_string())
+        );
+        assert!(explorer.query("ls", &["/e"]).is_err());
+    }
+
+    #[test]
+    fn test_large_input() {
+        // 1000 directories of 100 files each, visited one after another.
+        let mut input = "$ cd /\n$ ls\n".to_string();
+        input += &(0..1000).map(|i| format!("dir d{}\n", i)).collect::<String>();
+        for i in 0..1000 {
+            input += &format!("$ cd d{}\n$ ls\n", i);
+            input += &(0..100).map(|j| format!("500 f{}\n", j)).collect::<String>();
+            input += "$ cd ..\n";
+        }
+
+        // Each directory is small enough for part 1, but only deleting
+        // everything frees enough space.
+        let root = parse_input(&input).unwrap();
+        assert_eq!(process_part1(&root), 1000 * 50_000);
+        assert_eq!(process_part2(&root), 1000 * 50_000);
+    }
+
+    #[test]
+    fn test_deep_input() {
+        // 20000 directories, each inside the last, with a file at the bottom.
+        let mut input = "$ cd /\n".to_string();
+        for i in 0..20000 {
+            input += &format!("$ ls\ndir d{}\n$ cd d{}\n", i, i);
+        }
+        input += "$ ls\n50000000 f\n";
+
+        // Every directory holds the one file, too big for part 1, and
+        // deleting any of them frees enough space.
+        let tree = parse_input(&input).unwrap();
+        assert_eq!(process_part1(&tree), 0);
+        assert_eq!(process_part2(&tree), 50_000_000);
+        let explorer = Explorer { tree };
+        assert_eq!(explorer.query("size", &["/"]), Ok("50000000".to_string()));
+    }
+}
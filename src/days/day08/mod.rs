@@ -0,0 +1,127 @@
+use std::iter::once;
+
+use crate::{
+    error::{lines, ParseError, SolveError},
+    generate::Rng,
+    grid::{Grid, Point, ORTHOGONAL},
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+};
+
+/// The heights of a rectangle of trees, at least one.
+fn parse_input(input: &str) -> Result<Grid<char>, ParseError> {
+    let rows = lines(input).collect::<Vec<_>>();
+    let Some(width) = rows.first().map(|row| row.text.len()).filter(|&w| w > 0) else {
+        return Err(ParseError::end_of_input(0, "a row of trees"));
+    };
+    for row in &rows {
+        if let Some((i, c)) = row.text.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+            return Err(row.error(&row.text[i..i + c.len_utf8()], "a tree's height"));
+        }
+        if row.text.len() != width {
+            return Err(row.mismatch(&format!("a row of {} trees", width)));
+        }
+    }
+    Ok(Grid::parse(input, |c| c))
+}
+
+/// The trees seen looking out from `point` in each direction, nearest first.
+fn rays(field: &Grid<char>, point: Point) -> impl Iterator<Item = Vec<char>> + '_ {
+    ORTHOGONAL
+        .iter()
+        .map(move |d| field.ray(point, *d).map(|p| field[p]).collect())
+}
+
+fn process_part1(field: &Grid<char>) -> usize {
+    field
+        .points()
+        .filter(|&p| rays(field, p).any(|ray| ray.iter().all(|other| *other < field[p])))
+        .count()
+}
+
+fn score(field: &Grid<char>, point: Point) -> usize {
+    let this = field[point];
+    rays(field, point)
+        .map(|ray| {
+            let count = ray.iter().take_while(|other| **other < this).count();
+            if count == ray.len() {
+                count
+            } else {
+                count + 1
+            }
+        })
+        .product()
+}
+
+fn process_part2(field: &Grid<char>) -> usize {
+    field.points().map(|p| score(field, p)).max().unwrap()
+}
+
+/// A square forest `size` trees wide.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            (0..size.max(1))
+                .map(|_| char::from_digit(rng.below(10) as u32, 10).unwrap())
+                .chain(once('\n'))
+                .collect::<String>()
+        })
+        .collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 8;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a row of trees",
+        allowed: "a digit",
+        chars: |c| c.is_ascii_digit(),
+        grid: true,
+        ..Section::TEXT
+    }];
+
+    type Parsed = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(field: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(field).into())
+    }
+
+    fn part2(field: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(field).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(process_part1(&parse_input(INPUT).unwrap()), 21);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(process_part2(&parse_input(INPUT).unwrap()), 8);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(parse_input("").unwrap_err().expected, "a row of trees");
+        let error = parse_input("123\n4x6\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(parse_input("123\n45\n").unwrap_err().line, 2);
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashSet;
+
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::{Param, Params},
+    solution::{Answer, Solution},
+    trace,
+};
+
+type Vector = [i16; 2];
+
+/// Knots from the head to the tail.
+type Rope = [Vector];
+
+/// A direction and how many steps to take in it.
+type Move = (Vector, u8);
+
+fn format(value: &Rope) -> String {
+    let xs = value.iter().map(|[x, _]| *x).collect::<Vec<_>>();
+    let x_min = xs.iter().min().unwrap();
+    let x_max = xs.iter().max().unwrap();
+    let width: usize = (x_max - x_min + 1).try_into().unwrap();
+
+    let ys = value.iter().map(|[_, y]| *y).collect::<Vec<_>>();
+    let y_min = ys.iter().min().unwrap();
+    let y_max = ys.iter().max().unwrap();
+    let height: usize = (y_max - y_min + 1).try_into().unwrap();
+
+    let mut grid = vec![vec!['.'; width]; height];
+
+    for (index, [x, y]) in value.iter().enumerate() {
+        let nx: usize = (x - x_min).try_into().unwrap();
+        let ny: usize = (y - y_min).try_into().unwrap();
+        grid[ny][nx] = match grid[ny][nx] {
+            '.' => char::from_digit((index % 10) as u32, 10).unwrap(),
+            c => c,
+        };
+    }
+
+    grid.iter()
+        .map(|line| line.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn basis_vector(line: &Line, value: &str) -> Result<Vector, ParseError> {
+    match value {
+        "U" => Ok([0, -1]),
+        "D" => Ok([0, 1]),
+        "L" => Ok([-1, 0]),
+        "R" => Ok([1, 0]),
+        _ => Err(line.error(value, "a direction: U, D, L or R")),
+    }
+}
+
+fn move_vector(line: Line) -> Result<Move, ParseError> {
+    match line.text.split_whitespace().collect::<Vec<&str>>()[..] {
+        [dir, dist] => Ok((
+            basis_vector(&line, dir)?,
+            line.parse(dist, "a distance")?,
+        )),
+        _ => Err(line.mismatch("a move like `R 4`")),
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Move>, ParseError> {
+    lines(input).map(move_vector).collect()
+}
+
+/// A move as the input writes it, like `R 4`.
+fn format_move((dir, dist): Move) -> String {
+    let name = match dir {
+        [0, -1] => "U",
+        [0, 1] => "D",
+        [-1, 0] => "L",
+        _ => "R",
+    };
+    format!("{} {}", name, dist)
+}
+
+fn zip_vector([hx, hy]: Vector, [tx, ty]: Vector) -> [(i16, i16); 2] {
+    [(hx, tx), (hy, ty)]
+}
+
+fn catch_up(prev: &Vector, cur: &Vector) -> Vector {
+    let pairs = zip_vector(*prev, *cur);
+    if pairs.iter().all(|(h, t)| h.abs_diff(*t) <= 1) {
+        *cur
+    } else {
+        pairs.map(|(h, t)| t + (h - t).signum())
+    }
+}
+
+fn simulate_move(
+    rope: &mut Rope,
+    (dir, dist): Move,
+    tail_positions: &mut HashSet<Vector>,
+) {
+    for _ in 0..dist {
+        rope[0] = zip_vector(rope[0], dir).map(|(a, b)| a + b);
+        for i in 1..rope.len() {
+            rope[i] = catch_up(&rope[i - 1], &rope[i]);
+        }
+
+        tail_positions.extend(rope.last());
+    }
+}
+
+fn process_internal(rope: &mut Rope, agg: &mut HashSet<Vector>, moves: &[Move]) -> usize {
+    trace!(States, "{}\n", format(rope));
+    for &step in moves {
+        simulate_move(rope, step, agg);
+        trace!(Steps, "{}: {} tail positions", format_move(step), agg.len());
+        trace!(States, "{}\n", format(rope));
+    }
+
+    agg.len()
+}
+
+fn process(knots: usize, moves: &[Move]) -> usize {
+    let zero = [0; 2];
+    let mut agg = HashSet::<_>::from([zero]);
+    process_internal(&mut vec![zero; knots], &mut agg, moves)
+}
+
+const PART1_KNOTS: Param = Param {
+    name: "part1-knots",
+    default: 2,
+    range: 1..=1000,
+    help: "knots on the rope of part 1",
+};
+
+const PART2_KNOTS: Param = Param {
+    name: "part2-knots",
+    default: 10,
+    range: 1..=1000,
+    help: "knots on the rope of part 2",
+};
+
+/// `size` moves. The head is steered back towards the start once it strays
+/// far, so the rope stays well within the range of its coordinates.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    const LIMIT: i64 = 1000;
+
+    let mut head: (i64, i64) = (0, 0);
+    let mut result = String::new();
+    for _ in 0..size {
+        let dir = match (head.0.abs() > LIMIT, head.1.abs() > LIMIT) {
+            (true, _) if head.0 > 0 => 'L',
+            (true, _) => 'R',
+            (_, true) if head.1 > 0 => 'U',
+            (_, true) => 'D',
+            _ => *rng.pick(&['U', 'D', 'L', 'R']),
+        };
+        let dist = rng.range(1..=20);
+        match dir {
+            'U' => head.1 -= dist,
+            'D' => head.1 += dist,
+            'L' => head.0 -= dist,
+            _ => head.0 += dist,
+        }
+        result += &format!("{} {}\n", dir, dist);
+    }
+    result
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 9;
+    const PARAMS: &'static [Param] = &[PART1_KNOTS, PART2_KNOTS];
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a motion",
+        allowed: "`R`, `L`, `U`, `D`, a digit or a space",
+        chars: |c| "RLUD ".contains(c) || c.is_ascii_digit(),
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<Move>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(moves: &Self::Parsed, params: &Params) -> Result<Answer, SolveError> {
+        Ok(process(params.get(&PART1_KNOTS), moves).into())
+    }
+
+    fn part2(moves: &Self::Parsed, params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process(params.get(&PART2_KNOTS), moves).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    fn tail_positions(knots: usize, input: &str) -> usize {
+        process(knots, &parse_input(input).unwrap())
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(tail_positions(2, INPUT), 13);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(tail_positions(10, INPUT), 1);
+    }
+
+    #[test]
+    fn test_trace() {
+        let (result, out) = trace::capture(trace::Level::Steps, || tail_positions(2, INPUT));
+        assert_eq!(result, 13);
+        assert_eq!(out.lines().count(), INPUT.lines().count());
+        assert_eq!(out.lines().last(), Some("R 2: 13 tail positions"));
+
+        let (_, out) = trace::capture(trace::Level::States, || tail_positions(2, "R 1\n"));
+        assert_eq!(out, "0\n\nR 1: 1 tail positions\n10\n\n");
+    }
+
+    #[test]
+    fn test_large_input() {
+        // Once the rope has settled, walking the same square again visits
+        // nothing new.
+        let square = "R 5\nU 5\nL 5\nD 5\n";
+        let input = square.repeat(25_000);
+        let settled = square.repeat(2);
+        assert_eq!(tail_positions(2, &input), tail_positions(2, &settled));
+        assert_eq!(tail_positions(10, &input), tail_positions(10, &settled));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("R 4\nX 2").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "a direction: U, D, L or R");
+    }
+}
@@ -0,0 +1,184 @@
+use std::iter::from_fn;
+
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+    trace,
+};
+
+#[derive(Debug)]
+pub enum Instruction {
+    Noop,
+    Addx(i64),
+}
+
+fn parse_line(line: Line) -> Result<Instruction, ParseError> {
+    match line.text.split_whitespace().collect::<Vec<&str>>()[..] {
+        ["addx", value] => Ok(Instruction::Addx(line.parse(value, "a number")?)),
+        ["noop"] => Ok(Instruction::Noop),
+        _ => Err(line.mismatch("`noop` or `addx` and a number")),
+    }
+}
+
+/// The instructions, as long as `x` stays within 32 bits, so every signal
+/// strength fits in an `i64`.
+fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut x = 1_i64;
+    lines(input)
+        .map(|line| {
+            let instruction = parse_line(line)?;
+            if let Instruction::Addx(value) = instruction {
+                x = x
+                    .checked_add(value)
+                    .filter(|x| i32::try_from(*x).is_ok())
+                    .ok_or_else(|| line.mismatch("an `addx` keeping x within 32 bits"))?;
+            }
+            Ok(instruction)
+        })
+        .collect()
+}
+
+/// Draws a row of the screen with the sprite centred on `x`.
+fn sprite(x: i64) -> String {
+    (0..40)
+        .map(|i| if x.abs_diff(i) <= 1 { '#' } else { '.' })
+        .collect()
+}
+
+fn cycles(instructions: &[Instruction]) -> impl Iterator<Item = i64> + '_ {
+    let mut input = instructions.iter();
+    let mut add_value = None;
+    let mut x = 1;
+    let mut cycle = 0;
+    from_fn(move || {
+        cycle += 1;
+        if let Some(value) = add_value {
+            let snapshot = x;
+            x += value;
+            add_value = None;
+            return Some(snapshot);
+        }
+        match input.next()? {
+            Instruction::Addx(value) => {
+                trace!(Steps, "cycle {}: addx {}, x = {}", cycle, value, x);
+                add_value = Some(*value);
+            }
+            Instruction::Noop => trace!(Steps, "cycle {}: noop, x = {}", cycle, x),
+        }
+        trace!(States, "{}", sprite(x));
+        Some(x)
+    })
+}
+
+fn process_part1(instructions: &[Instruction]) -> i64 {
+    cycles(instructions)
+        .zip(1..)
+        .skip(20 - 1)
+        .step_by(40)
+        .take(6)
+        .map(|(a, b)| a * b)
+        .sum()
+}
+
+fn process_part2(instructions: &[Instruction]) -> String {
+    cycles(instructions)
+        .zip(0..)
+        .map(|(x, i)| if x.abs_diff(i % 40) <= 1 { '#' } else { '.' })
+        .collect::<Vec<char>>()
+        .chunks(40)
+        .map(|x| x.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// `size` instructions, and more if that isn't enough to draw the whole
+/// screen. Every `addx` keeps the sprite on the screen.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut x = 1;
+    let mut cycles = 0;
+    let mut result = String::new();
+    for i in 0.. {
+        if i >= size && cycles >= 240 {
+            break;
+        }
+        if rng.chance(1, 3) {
+            result += "noop\n";
+            cycles += 1;
+        } else {
+            let value = loop {
+                let value = rng.range(-20..=20);
+                if value != 0 && (0..40).contains(&(x + value)) {
+                    break value;
+                }
+            };
+            x += value;
+            result += &format!("addx {}\n", value);
+            cycles += 2;
+        }
+    }
+    result
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 10;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "an instruction",
+        allowed: "a lowercase letter, a digit, `-` or a space",
+        chars: |c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == ' ',
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(instructions: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(instructions).into())
+    }
+
+    fn part2(instructions: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(Answer::Image(process_part2(instructions))))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(process_part1(&parse_input(INPUT).unwrap()), 13140);
+    }
+
+    #[test]
+    fn test_part2() {
+        let expected = "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+        assert_eq!(process_part2(&parse_input(INPUT).unwrap()), expected);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("addx 2147483646\nnoop\naddx 1\n").unwrap_err();
+        assert_eq!(error.line, 3);
+        let error = parse_input("addx -9223372036854775808\n").unwrap_err();
+        assert_eq!(error.line, 1);
+    }
+}
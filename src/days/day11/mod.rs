@@ -0,0 +1,527 @@
+use crate::{
+    budget,
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::{Param, Params},
+    solution::{Answer, Solution},
+    trace,
+};
+
+mod reference;
+
+/// Wide enough for part 1, where worry levels aren't reduced.
+type Worry = u128;
+
+#[derive(Clone, Copy)]
+enum Operator {
+    Add,
+    Mul,
+}
+
+impl Operator {
+    /// `None` if the result doesn't fit in a [`Worry`].
+    fn apply(self, a: Worry, b: Worry) -> Option<Worry> {
+        match self {
+            Operator::Add => a.checked_add(b),
+            Operator::Mul => a.checked_mul(b),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Monkey {
+    items: Vec<Worry>,
+    operator: Option<Operator>,
+    operand: String,
+    divisor: usize,
+    target_true: usize,
+    target_false: usize,
+    inspected: usize,
+}
+
+impl Monkey {
+    fn new() -> Self {
+        Self {
+            items: vec![],
+            operator: None,
+            operand: "".to_string(),
+            divisor: 0,
+            target_true: usize::MAX,
+            target_false: usize::MAX,
+            inspected: 0,
+        }
+    }
+
+    /// Where each item is thrown, or `None` if a worry level outgrows a
+    /// [`Worry`]. Worry levels are kept modulo `modulus` if there is one.
+    fn inspect_items(
+        &mut self,
+        relief: Worry,
+        modulus: Option<Worry>,
+    ) -> Option<Vec<(Worry, usize)>> {
+        self.items
+            .iter()
+            .map(|item| {
+                let operand = match self.operand.as_str() {
+                    "old" => *item,
+                    n => n.parse().unwrap(),
+                };
+                let worry = self.operator.unwrap().apply(*item, operand)? / relief;
+                let next_item = match modulus {
+                    Some(modulus) => worry % modulus,
+                    None => worry,
+                };
+                let target = if next_item.is_multiple_of(self.divisor as Worry) {
+                    self.target_true
+                } else {
+                    self.target_false
+                };
+                self.inspected += 1;
+                Some((next_item, target))
+            })
+            .collect()
+    }
+}
+
+fn get_operator(line: &Line, input: &str) -> Result<Operator, ParseError> {
+    match input {
+        "+" => Ok(Operator::Add),
+        "*" => Ok(Operator::Mul),
+        _ => Err(line.error(input, "an operator: + or *")),
+    }
+}
+
+fn parse_op(line: &Line, input: &str) -> Result<(Option<Operator>, String), ParseError> {
+    match input.split_whitespace().collect::<Vec<&str>>()[..] {
+        ["new", "=", "old", op, b] => {
+            if b != "old" && b.parse::<usize>().is_err() {
+                return Err(line.error(b, "`old` or a number"));
+            }
+            Ok((Some(get_operator(line, op)?), b.to_string()))
+        }
+        _ => Err(line.error(input, "an operation like `new = old * 19`")),
+    }
+}
+
+fn parse_suffix(line: &Line, input: &str, prefix: &str, expected: &str) -> Result<usize, ParseError> {
+    match input.strip_prefix(prefix) {
+        Some(value) => line.parse(value, expected),
+        None => Err(line.error(input, &format!("`{}` and {}", prefix.trim_end(), expected))),
+    }
+}
+
+/// A monkey number to throw to, which is added to `targets` along with an
+/// error pointing at it, since whether that monkey exists is only known once
+/// they all are read.
+fn parse_target(
+    line: &Line,
+    input: &str,
+    targets: &mut Vec<(usize, ParseError)>,
+) -> Result<usize, ParseError> {
+    let target = parse_suffix(line, input, "throw to monkey ", "a monkey number")?;
+    let number = input.strip_prefix("throw to monkey ").unwrap_or(input);
+    targets.push((target, line.error(number, "a monkey number")));
+    Ok(target)
+}
+
+/// The first line `monkey` is missing, if any.
+fn missing(monkey: &Monkey) -> Option<&'static str> {
+    if monkey.operator.is_none() {
+        Some("an `Operation:` line")
+    } else if monkey.divisor == 0 {
+        Some("a `Test:` line")
+    } else if monkey.target_true == usize::MAX {
+        Some("an `If true:` line")
+    } else if monkey.target_false == usize::MAX {
+        Some("an `If false:` line")
+    } else {
+        None
+    }
+}
+
+/// Reads the next monkey, which ends at a blank line or the end of the input.
+fn parse_monkey<'a>(
+    input: &mut impl Iterator<Item = Line<'a>>,
+    targets: &mut Vec<(usize, ParseError)>,
+) -> Result<Option<Monkey>, ParseError> {
+    let mut monkey = None;
+    let mut last = 0;
+    for line in input {
+        let fields = line.text.split(':').map(|s| s.trim()).collect::<Vec<&str>>();
+
+        match (monkey.as_mut(), &fields[..]) {
+            (Some(m), [""]) => {
+                return match missing(m) {
+                    Some(expected) => {
+                        Err(ParseError::new(line.number, 1, expected, "a blank line"))
+                    }
+                    None => Ok(monkey),
+                };
+            }
+            (None, [""]) => (),
+            (None, [header, ""]) if header.starts_with("Monkey ") => {
+                monkey = Some(Monkey::new())
+            }
+            (None, _) => return Err(line.mismatch("a `Monkey N:` header")),
+            (Some(_), ["Starting items", ""]) => {}
+            (Some(m), ["Starting items", items]) => {
+                for n in items.split(',') {
+                    m.items.push(line.parse(n.trim(), "a worry level")?);
+                }
+            }
+            (Some(m), ["Operation", op]) => {
+                (m.operator, m.operand) = parse_op(&line, op)?;
+            }
+            (Some(m), ["Test", test]) => {
+                m.divisor = parse_suffix(&line, test, "divisible by ", "a divisor above 0")?;
+                if m.divisor == 0 {
+                    let divisor = test.strip_prefix("divisible by ").unwrap_or(test);
+                    return Err(line.error(divisor, "a divisor above 0"));
+                }
+            }
+            (Some(m), ["If true", target]) => {
+                m.target_true = parse_target(&line, target, targets)?;
+            }
+            (Some(m), ["If false", target]) => {
+                m.target_false = parse_target(&line, target, targets)?;
+            }
+            (Some(_), _) => {
+                return Err(line.mismatch("a monkey's items, operation, test or target"));
+            }
+        }
+        last = line.number;
+    }
+
+    match monkey.as_ref().and_then(missing) {
+        Some(expected) => Err(ParseError::end_of_input(last, expected)),
+        None => Ok(monkey),
+    }
+}
+
+fn parse_input<'a>(mut input: impl Iterator<Item = Line<'a>>) -> Result<Vec<Monkey>, ParseError> {
+    let mut monkeys = vec![];
+    let mut targets = vec![];
+    while let Some(monkey) = parse_monkey(&mut input, &mut targets)? {
+        monkeys.push(monkey);
+    }
+
+    let count = monkeys.len();
+    match targets.into_iter().find(|&(target, _)| target >= count) {
+        Some((_, error)) => Err(ParseError {
+            expected: format!("a monkey number below {}", count),
+            ..error
+        }),
+        None => Ok(monkeys),
+    }
+}
+
+/// Lists the items each monkey holds, one monkey per line.
+fn format(monkeys: &[Monkey]) -> String {
+    monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let items = m.items.iter().map(|item| item.to_string()).collect::<Vec<_>>();
+            format!("Monkey {}: {}", i, items.join(", ")).trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn round(relief: Worry, modulus: Option<Worry>, monkeys: &mut [Monkey]) -> Option<()> {
+    for i in 0..monkeys.len() {
+        let throws = monkeys[i].inspect_items(relief, modulus)?;
+        monkeys[i].items.clear();
+        for (item, target) in throws {
+            monkeys[target].items.push(item);
+        }
+    }
+    Some(())
+}
+
+fn gcd(a: Worry, b: Worry) -> Worry {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The least common multiple of the monkeys' divisors, which worry levels can
+/// be kept modulo without changing any test; `None` if it outgrows a `u64`,
+/// past which squaring a worry level could overflow anyway.
+fn common_multiple(monkeys: &[Monkey]) -> Option<Worry> {
+    monkeys.iter().try_fold(1, |lcm: Worry, monkey| {
+        let divisor = monkey.divisor as Worry;
+        Some(lcm / gcd(lcm, divisor) * divisor).filter(|&lcm| lcm <= u64::MAX as Worry)
+    })
+}
+
+/// The monkey business after `rounds`, or `None` if a worry level or the
+/// business itself grows too large, or the budget is spent first.
+fn process(relief: Worry, rounds: usize, monkeys: &[Monkey]) -> Option<usize> {
+    let mut monkeys = monkeys.to_vec();
+    // Reducing is only sound without relief: it doesn't commute with the
+    // division.
+    let modulus = if relief == 1 {
+        common_multiple(&monkeys)
+    } else {
+        None
+    };
+
+    for n in 1..=rounds {
+        if budget::spent() {
+            return None;
+        }
+        round(relief, modulus, &mut monkeys)?;
+        trace!(
+            Steps,
+            "after round {}, inspections: {:?}",
+            n,
+            monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>()
+        );
+        trace!(States, "{}\n", format(&monkeys));
+    }
+
+    let mut inspecteds = monkeys.iter().map(|m| m.inspected).collect::<Vec<usize>>();
+    inspecteds.sort();
+    inspecteds.reverse();
+    inspecteds.iter().take(2).try_fold(1, |business, &n| n.checked_mul(business))
+}
+
+const PART1_ROUNDS: Param = Param {
+    name: "part1-rounds",
+    default: 20,
+    range: 0..=i64::MAX,
+    help: "rounds played in part 1",
+};
+
+const PART1_RELIEF: Param = Param {
+    name: "part1-relief",
+    default: 3,
+    range: 1..=i64::MAX,
+    help: "what worry levels are divided by after each inspection in part 1",
+};
+
+const PART2_ROUNDS: Param = Param {
+    name: "part2-rounds",
+    default: 10000,
+    range: 0..=i64::MAX,
+    help: "rounds played in part 2",
+};
+
+/// `count` monkeys, one of which squares. Each tests divisibility by one of
+/// the first nine primes, as the puzzle's do.
+fn generate_monkeys(rng: &mut Rng, count: usize) -> String {
+    const PRIMES: [usize; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+    let squaring = rng.below(count);
+    let mut monkeys = vec![];
+    for i in 0..count {
+        let items = (0..rng.range(0..=8))
+            .map(|_| format!(" {}", rng.range(50..=99)))
+            .collect::<Vec<_>>();
+        let operation = match rng.below(3) {
+            _ if i == squaring => "old * old".to_string(),
+            0 => format!("old * {}", rng.range(2..=19)),
+            _ => format!("old + {}", rng.range(1..=8)),
+        };
+        let target_true = (i + 1 + rng.below(count - 1)) % count;
+        let target_false = loop {
+            let target = rng.below(count);
+            if target != i && (target != target_true || count == 2) {
+                break target;
+            }
+        };
+        monkeys.push(format!(
+            "Monkey {}:\n  Starting items:{}\n  Operation: new = {}\n  \
+             Test: divisible by {}\n    If true: throw to monkey {}\n    \
+             If false: throw to monkey {}\n",
+            i,
+            items.join(","),
+            operation,
+            rng.pick(&PRIMES),
+            target_true,
+            target_false
+        ));
+    }
+    monkeys.join("\n")
+}
+
+/// `size` monkeys, at least two. Draws again until part 1's worry levels fit
+/// in a [`Worry`], as the puzzle's do.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    loop {
+        let input = generate_monkeys(rng, size.max(2));
+        if let Ok(Some(_)) = reference::part1(&input) {
+            return input;
+        }
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 11;
+    const PARAMS: &'static [Param] = &[PART1_ROUNDS, PART1_RELIEF, PART2_ROUNDS];
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a line of a monkey's notes",
+        count: 6..=6,
+        repeats: true,
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(lines(input))
+    }
+
+    fn part1(monkeys: &Self::Parsed, params: &Params) -> Result<Answer, SolveError> {
+        let (relief, rounds) = (params.get(&PART1_RELIEF), params.get(&PART1_ROUNDS));
+        match process(relief, rounds, monkeys) {
+            Some(business) => Ok(business.into()),
+            None => Err(SolveError::no_answer("worry levels that fit in 128 bits")),
+        }
+    }
+
+    fn part2(monkeys: &Self::Parsed, params: &Params) -> Result<Option<Answer>, SolveError> {
+        let rounds = params.get(&PART2_ROUNDS);
+        match process(1, rounds, monkeys) {
+            Some(business) => Ok(Some(business.into())),
+            None => Err(SolveError::no_answer("monkey business that fits in a `usize`")),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn reference(part: u8, input: &str) -> Result<Option<String>, ParseError> {
+        Ok(match part {
+            1 => reference::part1(input)?.map(|n| n.to_string()),
+            2 => Some(reference::part2(input)?.to_string()),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    fn monkey_business(relief: Worry, rounds: usize, input: &str) -> usize {
+        process(relief, rounds, &parse_input(lines(input)).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        let result = monkey_business(3, 20, INPUT);
+        assert_eq!(result, 10605);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = monkey_business(1, 10000, INPUT);
+        assert_eq!(result, 2713310158);
+    }
+
+    #[test]
+    fn test_reference() {
+        assert_eq!(reference::part1(INPUT), Ok(Some(10605)));
+        assert_eq!(reference::part2(INPUT), Ok(2713310158));
+    }
+
+    #[test]
+    fn test_trimmed_input() {
+        let result = monkey_business(3, 20, INPUT.trim_end());
+        assert_eq!(result, 10605);
+    }
+
+    #[test]
+    fn test_large_input() {
+        // A single item passed down a line of 20000 monkeys, which each
+        // inspect it once a round.
+        let n = 20_000;
+        let input = (0..n)
+            .map(|i| {
+                format!(
+                    "Monkey {i}:\n  Starting items: {}\n  Operation: new = old + 1\n  \
+                     Test: divisible by 2\n    If true: throw to monkey {next}\n    \
+                     If false: throw to monkey {next}\n",
+                    if i == 0 { "1" } else { "" },
+                    next = (i + 1) % n
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(monkey_business(3, 20, &input), 20 * 20);
+    }
+
+    #[test]
+    fn test_param_bounds() {
+        let monkeys = parse_input(lines(INPUT)).unwrap();
+        let params = |name, value: i64| {
+            let mut params = Params::default();
+            params.set(Solver::PARAMS, name, &value.to_string()).unwrap();
+            params
+        };
+        let part2 = |params| Solver::part2(&monkeys, &params).map(Option::unwrap);
+        assert_eq!(Solver::part1(&monkeys, &params("part1-rounds", 0)), Ok(Answer::Integer(0)));
+        assert_eq!(part2(params("part2-rounds", 0)), Ok(Answer::Integer(0)));
+        assert_eq!(
+            Solver::part1(&monkeys, &params("part1-relief", i64::MAX)),
+            Ok(Answer::Integer(38407))
+        );
+
+        // With relief, worry levels aren't reduced and soon outgrow 128 bits.
+        assert_eq!(
+            Solver::part1(&monkeys, &params("part1-rounds", i64::MAX)),
+            Err(SolveError::no_answer("worry levels that fit in 128 bits"))
+        );
+        // Without, they stay small, and only the budget ends this many rounds.
+        let budget = budget::Budget::unlimited();
+        budget.cancel();
+        let (result, stopped) = budget::limit(&budget, || part2(params("part2-rounds", i64::MAX)));
+        assert!(result.is_err() && stopped);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = |input: &str| parse_input(lines(input)).err().unwrap();
+        let input = "Monkey 0:\n  Operation: new = old ^ 2";
+        let e = error(input);
+        assert_eq!((e.line, e.column), (2, 24));
+        assert_eq!(e.expected, "an operator: + or *");
+
+        let monkey = "Monkey 0:\n  Starting items: 79\n  Operation: new = old * 19\n  \
+                      Test: divisible by 23\n    If true: throw to monkey 0\n    \
+                      If false: throw to monkey 0";
+        assert!(parse_input(lines(monkey)).is_ok());
+        let e = error(&monkey.replace("If true: throw to monkey 0", "If true: throw to monkey 5"));
+        assert_eq!((e.line, e.column), (5, 30));
+        assert_eq!(e.expected, "a monkey number below 1");
+        let e = error(&monkey.replace("\n    If false: throw to monkey 0", ""));
+        assert_eq!((e.line, e.expected.as_str()), (6, "an `If false:` line"));
+        let e = error(&monkey.replace("  Operation: new = old * 19\n", ""));
+        assert_eq!((e.line, e.expected.as_str()), (6, "an `Operation:` line"));
+        let e = error(&monkey.replace("Test: divisible by 23\n", "\n"));
+        assert_eq!((e.line, e.expected.as_str()), (4, "a `Test:` line"));
+        let e = error(&monkey.replace("by 23", "by 0"));
+        assert_eq!((e.line, e.column, e.expected.as_str()), (4, 22, "a divisor above 0"));
+    }
+
+    #[test]
+    fn test_other_divisors() {
+        // 29 and 31 aren't among the primes the generator picks from.
+        let input = INPUT.replace("divisible by 23", "divisible by 29");
+        let input = input.replace("divisible by 19", "divisible by 31");
+        assert_eq!(
+            monkey_business(1, 10000, &input),
+            reference::part2(&input).unwrap()
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! Solutions that don't keep worry levels modulo a common multiple of the
+//! monkeys' divisors.
+
+use super::{lines, parse_input, Monkey, Operator, ParseError};
+
+fn business(mut inspected: Vec<usize>) -> usize {
+    inspected.sort();
+    inspected.iter().rev().take(2).product()
+}
+
+fn operand(monkey: &Monkey, old: u128) -> u128 {
+    match monkey.operand.as_str() {
+        "old" => old,
+        n => n.parse().unwrap(),
+    }
+}
+
+/// Keeps the worry levels exact; `None` once one outgrows a `u128`.
+pub fn part1(input: &str) -> Result<Option<usize>, ParseError> {
+    let monkeys = parse_input(lines(input))?;
+    let mut items = monkeys.iter().map(|m| m.items.clone()).collect::<Vec<_>>();
+    let mut inspected = vec![0; monkeys.len()];
+
+    for _ in 0..20 {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for old in std::mem::take(&mut items[i]) {
+                let new = match monkey.operator.unwrap() {
+                    Operator::Add => old.checked_add(operand(monkey, old)),
+                    Operator::Mul => old.checked_mul(operand(monkey, old)),
+                };
+                let Some(new) = new.map(|new| new / 3) else {
+                    return Ok(None);
+                };
+                let target = if new.is_multiple_of(monkey.divisor as u128) {
+                    monkey.target_true
+                } else {
+                    monkey.target_false
+                };
+                inspected[i] += 1;
+                items[target].push(new);
+            }
+        }
+    }
+
+    Ok(Some(business(inspected)))
+}
+
+/// Keeps each worry level as its remainders by every monkey's divisor,
+/// which is all the tests look at.
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    let monkeys = parse_input(lines(input))?;
+    let divisors = monkeys
+        .iter()
+        .map(|m| m.divisor as u128)
+        .collect::<Vec<_>>();
+    let mut items = monkeys
+        .iter()
+        .map(|m| {
+            m.items
+                .iter()
+                .map(|&i| divisors.iter().map(|d| i % d).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut inspected = vec![0; monkeys.len()];
+
+    for _ in 0..10000 {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for old in std::mem::take(&mut items[i]) {
+                let new = old
+                    .iter()
+                    .zip(&divisors)
+                    .map(|(&r, d)| {
+                        // Both are below `d`, so this can't overflow.
+                        let operand = operand(monkey, r) % d;
+                        monkey.operator.unwrap().apply(r, operand).unwrap() % d
+                    })
+                    .collect::<Vec<_>>();
+                let target = if new[i] == 0 {
+                    monkey.target_true
+                } else {
+                    monkey.target_false
+                };
+                inspected[i] += 1;
+                items[target].push(new);
+            }
+        }
+    }
+
+    Ok(business(inspected))
+}
@@ -0,0 +1,202 @@
+use crate::{
+    error::{ParseError, SolveError},
+    generate::Rng,
+    grid::{Grid, Point},
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+};
+
+type Heights = Grid<u32>;
+
+/// The start, the end and the heightmap.
+type Map = (Point, Point, Heights);
+
+fn parse_input(input: &str) -> Result<Map, ParseError> {
+    let chars = Grid::parse(input, |c| c);
+    if let Some((x, y)) = chars.position(|c| !matches!(c, 'a'..='z' | 'S' | 'E')) {
+        let found = format!("`{}`", chars[(x, y)]);
+        return Err(ParseError::new(y + 1, x + 1, "a height from a to z, S or E", &found));
+    }
+
+    let marker = |c, expected| {
+        chars
+            .position(|other| *other == c)
+            .ok_or_else(|| ParseError::end_of_input(chars.height(), expected))
+    };
+    let start = marker('S', "the start, marked S")?;
+    let end = marker('E', "the end, marked E")?;
+
+    let grid = chars.map(|c| match c {
+        'S' => 0,
+        'E' => 25,
+        _ => c.to_digit(36).unwrap() - 10,
+    });
+
+    Ok((start, end, grid))
+}
+
+/// Counts the steps from `end` back to the nearest point satisfying `done`,
+/// one ring of newly reached points at a time; `None` if there is no path.
+fn find_path<F: Fn(Point) -> bool>(done: F, grid: &Heights, end: Point) -> Option<usize> {
+    let mut visits = grid.map(|_| false);
+    visits[end] = true;
+
+    let mut frontier = vec![end];
+    let mut steps = 0;
+    while !frontier.iter().any(|p| done(*p)) {
+        let mut next_frontier = vec![];
+        for p in frontier {
+            for n in grid.neighbors4(p) {
+                if !visits[n] && grid[p] <= grid[n] + 1 {
+                    visits[n] = true;
+                    next_frontier.push(n);
+                }
+            }
+        }
+
+        if next_frontier.is_empty() {
+            return None;
+        }
+        frontier = next_frontier;
+        steps += 1;
+    }
+
+    Some(steps)
+}
+
+fn process_part1((start, end, grid): &Map) -> Option<usize> {
+    find_path(|p| p == *start, grid, *end)
+}
+
+fn process_part2((_, end, grid): &Map) -> Option<usize> {
+    find_path(|p| grid[p] == 0, grid, *end)
+}
+
+/// A heightmap `size` columns wide, at least 26, and a quarter as high, with
+/// a gentle climb from `S` on the left edge to `E` on the right one.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(26);
+    let height = (width / 4).max(1);
+    let mut grid = Grid::new(width, height, 'a').map(|_| (b'a' + rng.below(26) as u8) as char);
+
+    let (mut x, mut y) = (0, rng.below(height));
+    let end_y = rng.below(height);
+    let mut climb = vec![(x, y)];
+    while (x, y) != (width - 1, end_y) {
+        if y == end_y || x < width - 1 && rng.chance(1, 2) {
+            x += 1;
+        } else if y < end_y {
+            y += 1;
+        } else {
+            y -= 1;
+        }
+        climb.push((x, y));
+    }
+
+    let steps = climb.len() - 1;
+    for (i, &p) in climb.iter().enumerate() {
+        grid[p] = (b'a' + (25 * i / steps) as u8) as char;
+    }
+    grid[climb[0]] = 'S';
+    grid[climb[steps]] = 'E';
+
+    grid.rows()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 12;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a row of the heightmap",
+        allowed: "a lowercase letter, `S` or `E`",
+        chars: |c| c.is_ascii_lowercase() || c == 'S' || c == 'E',
+        grid: true,
+        ..Section::TEXT
+    }];
+
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(map: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        match process_part1(map) {
+            Some(steps) => Ok(steps.into()),
+            None => Err(SolveError::no_answer("path from the start to the end")),
+        }
+    }
+
+    fn part2(map: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        match process_part2(map) {
+            Some(steps) => Ok(Some(steps.into())),
+            None => Err(SolveError::no_answer("path from an `a` to the end")),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, Some(31));
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(result, Some(29));
+    }
+
+    #[test]
+    fn test_no_path() {
+        let map = parse_input("SzE\n").unwrap();
+        assert_eq!(process_part1(&map), None);
+        assert_eq!(
+            Solver::part2(&map, &Params::default()),
+            Err(SolveError::no_answer("path from an `a` to the end"))
+        );
+    }
+
+    #[test]
+    fn test_large_input() {
+        // A path snaking through 101 rows of `a`, between walls of `z` with a
+        // gap at alternating ends, and up a ramp to E at the very end.
+        let (width, rows) = (200, 101);
+        let mut lines = vec![];
+        for row in 0..rows {
+            let mut line = "a".repeat(width);
+            if row == 0 {
+                line.replace_range(..1, "S");
+            }
+            if row == rows - 1 {
+                line.replace_range(width - 25.., "bcdefghijklmnopqrstuvwxyE");
+            }
+            lines.push(line);
+
+            if row < rows - 1 {
+                let mut wall = "z".repeat(width);
+                let gap = if row % 2 == 0 { width - 1 } else { 0 };
+                wall.replace_range(gap..gap + 1, "a");
+                lines.push(wall);
+            }
+        }
+        let map = parse_input(&lines.join("\n")).unwrap();
+
+        assert_eq!(process_part1(&map), Some(rows * width + (rows - 1) - 1));
+        assert_eq!(process_part2(&map), Some(25));
+    }
+}
@@ -0,0 +1,244 @@
+use std::{cmp::Ordering, slice, str::Chars};
+
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+};
+
+#[derive(Clone, Eq)]
+pub enum Value {
+    Single(usize),
+    List(Vec<Value>),
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Single(l0), Self::Single(r0)) => l0 == r0,
+            (Self::List(l0), Self::List(r0)) => l0 == r0,
+            _ => false,
+        }
+    }
+}
+
+/// Lists compare item by item, the shorter first if one runs out; a number
+/// compared with a list is taken as a list of just that number.
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::Single(a), Value::Single(b)) => a.cmp(b),
+            (Value::List(a), Value::List(b)) => a.cmp(b),
+            (Value::Single(_), Value::List(b)) => slice::from_ref(self).cmp(b),
+            (Value::List(a), Value::Single(_)) => a[..].cmp(slice::from_ref(other)),
+        }
+    }
+}
+
+fn parse_list(line: &Line, input: &mut Chars) -> Result<Value, ParseError> {
+    let mut values = vec![];
+    if input.as_str().starts_with(']') {
+        input.next();
+        return Ok(Value::List(values));
+    }
+
+    loop {
+        values.push(parse_value(line, input)?);
+        let rest = input.as_str();
+        match input.next() {
+            Some(',') => {}
+            Some(']') => return Ok(Value::List(values)),
+            c => {
+                let at = &rest[..c.map_or(0, char::len_utf8)];
+                return Err(line.error(at, "`,` or `]`"));
+            }
+        }
+    }
+}
+
+fn parse_value(line: &Line, input: &mut Chars) -> Result<Value, ParseError> {
+    let rest = input.as_str();
+    if rest.starts_with('[') {
+        input.next();
+        return parse_list(line, input);
+    }
+
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 {
+        return Err(line.error(rest, "a number or a list"));
+    }
+    *input = rest[digits..].chars();
+    line.parse(&rest[..digits], "a number").map(Value::Single)
+}
+
+fn parse_packet(line: &Line) -> Result<Value, ParseError> {
+    let mut input = line.text.chars();
+    let value = parse_value(line, &mut input)?;
+    match input.as_str() {
+        "" => Ok(value),
+        rest => Err(line.error(rest, "end of line")),
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<(Value, Value)>, ParseError> {
+    let mut pairs = vec![];
+
+    let mut pair = vec![];
+    for line in lines(input) {
+        match line.text {
+            "" if pair.is_empty() => {}
+            "" if pair.len() == 1 => return Err(line.mismatch("the second packet of a pair")),
+            "" => {
+                let right = pair.pop().unwrap();
+                pairs.push((pair.pop().unwrap(), right));
+            }
+            _ if pair.len() == 2 => return Err(line.mismatch("a blank line between pairs")),
+            _ => {
+                pair.push(parse_packet(&line)?);
+            }
+        }
+    }
+    match pair.len() {
+        0 => {}
+        1 => {
+            let count = input.lines().count();
+            return Err(ParseError::end_of_input(count, "the second packet of a pair"));
+        }
+        _ => {
+            let right = pair.pop().unwrap();
+            pairs.push((pair.pop().unwrap(), right));
+        }
+    }
+
+    Ok(pairs)
+}
+
+fn process_part1(pairs: &[(Value, Value)]) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, (left, right))| left <= right)
+        .map(|(i, _)| i + 1)
+        .sum()
+}
+
+fn divider(value: usize) -> Value {
+    Value::List(vec![Value::List(vec![Value::Single(value)])])
+}
+
+fn process_part2(pairs: &[(Value, Value)]) -> usize {
+    let mut values = pairs
+        .iter()
+        .flat_map(|(left, right)| [left.clone(), right.clone()])
+        .collect::<Vec<Value>>();
+
+    values.push(divider(2));
+    values.push(divider(6));
+
+    values.sort();
+
+    values
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| **v == divider(2) || **v == divider(6))
+        .map(|(i, _)| i + 1)
+        .product()
+}
+
+/// A packet: a list of up to four numbers and lists, nested at most `depth`
+/// levels deeper.
+fn generate_packet(rng: &mut Rng, depth: usize) -> String {
+    let values = (0..rng.below(5))
+        .map(|_| {
+            if depth > 0 && rng.chance(1, 3) {
+                generate_packet(rng, depth - 1)
+            } else {
+                rng.below(11).to_string()
+            }
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", values.join(","))
+}
+
+/// `size` pairs of packets.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{}\n{}\n", generate_packet(rng, 3), generate_packet(rng, 3)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 13;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a packet",
+        allowed: "a digit, `[`, `]` or `,`",
+        chars: |c| c.is_ascii_digit() || "[],".contains(c),
+        count: 2..=2,
+        repeats: true,
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<(Value, Value)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(pairs: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(pairs).into())
+    }
+
+    fn part2(pairs: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(pairs).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 13);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 140);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("[1,[2]]\n[1,[2;3]]").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(error.expected, "`,` or `]`");
+    }
+
+    #[test]
+    fn test_compare() {
+        let packet = |text| parse_packet(&lines(text).next().unwrap()).unwrap();
+        assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+        assert!(packet("[]") < packet("3"));
+        assert!(packet("[[[]]]") > packet("[[]]"));
+        assert_eq!(packet("7").cmp(&packet("[[7]]")), Ordering::Equal);
+    }
+}
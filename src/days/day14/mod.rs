@@ -0,0 +1,272 @@
+use std::{
+    cmp::{max, min},
+    ops::RangeInclusive,
+};
+
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    grid::{Coord, Grid},
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+    trace,
+};
+
+#[derive(Clone, Copy, Debug)]
+enum Cell {
+    Air,
+    Rock,
+    Sand,
+}
+
+struct Cave {
+    cells: Grid<Cell>,
+}
+
+type Point = (usize, usize);
+
+const INIT_POINT: Point = (500, 0);
+
+/// Part 2's floor spans columns 0 to 1000, wide enough for the pile of sand
+/// only while the floor lies no deeper than row 500, two below the lowest
+/// rock. Bounding the points also keeps the cave's grid small.
+const MAX_COL: usize = 1000;
+const MAX_ROW: usize = 498;
+
+fn coord((col, row): Point) -> Coord {
+    (col as isize, row as isize)
+}
+
+impl Cave {
+    fn new(rock_ranges: Vec<RangeInclusive<Point>>) -> Self {
+        let col_range = rock_ranges.iter().fold(INIT_POINT..=INIT_POINT, |a, b| {
+            min(*a.start(), *b.start())..=max(*a.end(), *b.end())
+        });
+
+        let base_col = col_range.start().0;
+        let width = col_range.end().0 - col_range.start().0 + 1;
+        let height = rock_ranges
+            .iter()
+            .map(|x| max(x.start().1, x.end().1))
+            .max()
+            .unwrap()
+            + 1;
+
+        let mut cells = Grid::new(width, height, Cell::Air).with_origin((base_col as isize, 0));
+        for rock_range in rock_ranges {
+            for col in rock_range.start().0..=rock_range.end().0 {
+                (rock_range.start().1..=rock_range.end().1).for_each(|row| {
+                    *cells.at_mut(coord((col, row))).unwrap() = Cell::Rock;
+                });
+            }
+        }
+
+        Self { cells }
+    }
+
+    fn subslice(&self, point: Point) -> [Option<Cell>; 3] {
+        let (col, row) = coord(point);
+        [-1, 0, 1].map(|offset| self.cells.at((col + offset, row + 1)).copied())
+    }
+
+    /// Where the grain poured at `point` comes to rest, if it does.
+    fn pour(&mut self, point: Point) -> Option<Point> {
+        match self.subslice(point) {
+            [_, Some(Cell::Air), _] => self.pour((point.0, point.1 + 1)),
+            [_, None, _] => None,
+            [Some(Cell::Air), _, _] => self.pour((point.0 - 1, point.1 + 1)),
+            [None, _, _] => None,
+            [_, _, Some(Cell::Air)] => self.pour((point.0 + 1, point.1 + 1)),
+            [_, _, None] => None,
+            _ if point == INIT_POINT => None,
+            _ => {
+                *self.cells.at_mut(coord(point)).unwrap() = Cell::Sand;
+                Some(point)
+            }
+        }
+    }
+
+    /// Pours grains until one doesn't come to rest, and counts those that did.
+    fn fill(&mut self) -> usize {
+        let mut count = 0;
+        while let Some((col, row)) = self.pour(INIT_POINT) {
+            count += 1;
+            trace!(Steps, "grain {} rests at {},{}", count, col, row);
+            trace!(States, "{}\n", self.format());
+        }
+        count
+    }
+
+    fn format(&self) -> String {
+        self.cells.render(|cell| match cell {
+            Cell::Air => '.',
+            Cell::Rock => '#',
+            Cell::Sand => 'o',
+        })
+    }
+}
+
+fn parse_segment(line: &Line, input: &str) -> Result<Point, ParseError> {
+    let input = input.trim();
+    match input.split(',').collect::<Vec<&str>>()[..] {
+        [a, b] => {
+            let col = line.parse(a, "a column")?;
+            if col > MAX_COL {
+                return Err(line.error(a, "a column up to 1000"));
+            }
+            let row = line.parse(b, "a row")?;
+            if row > MAX_ROW {
+                return Err(line.error(b, "a row up to 498"));
+            }
+            Ok((col, row))
+        }
+        _ => Err(line.error(input, "a point like `498,4`")),
+    }
+}
+
+fn parse_line(line: Line) -> Result<Vec<RangeInclusive<Point>>, ParseError> {
+    let points = line
+        .text
+        .split("->")
+        .map(|s| Ok((s.trim(), parse_segment(&line, s)?)))
+        .collect::<Result<Vec<(&str, Point)>, ParseError>>()?;
+
+    if points.len() < 2 {
+        return Err(line.mismatch("a path of at least two points"));
+    }
+
+    points
+        .windows(2)
+        .map(|pair| match pair {
+            [(_, a), (_, b)] if a.0 == b.0 || a.1 == b.1 => {
+                if a <= b {
+                    Ok(*a..=*b)
+                } else {
+                    Ok(*b..=*a)
+                }
+            }
+            [_, (at, _)] => Err(line.error(at, "a point in line with the previous one")),
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+fn parse_input(input: &str) -> Result<Vec<RangeInclusive<Point>>, ParseError> {
+    let mut ranges = vec![];
+    for line in lines(input) {
+        ranges.append(&mut parse_line(line)?);
+    }
+
+    if ranges.is_empty() {
+        return Err(ParseError::end_of_input(0, "a rock path"));
+    }
+    Ok(ranges)
+}
+
+fn process_part1(ranges: &[RangeInclusive<Point>]) -> usize {
+    let mut cave = Cave::new(ranges.to_vec());
+    cave.fill()
+}
+
+fn process_part2(ranges: &[RangeInclusive<Point>]) -> usize {
+    let mut ranges = ranges.to_vec();
+
+    let floor_y = ranges
+        .iter()
+        .map(|x| max(x.start().1, x.end().1))
+        .max()
+        .unwrap()
+        + 2;
+
+    ranges.push((0, floor_y)..=(MAX_COL, floor_y));
+
+    let mut cave = Cave::new(ranges);
+    cave.fill() + 1
+}
+
+/// `size` rock paths, kept close enough to the sand's source that the floor
+/// of part 2 catches all of it.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut result = String::new();
+    for _ in 0..size.max(1) {
+        let mut point = (rng.range(440..=560), rng.range(13..=170));
+        let mut points = vec![point];
+        for _ in 0..rng.range(1..=4) {
+            let step = rng.range(1..=12) * *rng.pick(&[-1, 1]);
+            point = if rng.chance(1, 2) {
+                ((point.0 + step).clamp(420, 580), point.1)
+            } else {
+                (point.0, (point.1 + step).clamp(2, 175))
+            };
+            points.push(point);
+        }
+
+        let points = points
+            .iter()
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect::<Vec<_>>();
+        result += &format!("{}\n", points.join(" -> "));
+    }
+    result
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 14;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a rock path",
+        allowed: "a digit, `,`, `->` or a space",
+        chars: |c| c.is_ascii_digit() || ", ->".contains(c),
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<RangeInclusive<Point>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(ranges: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(ranges).into())
+    }
+
+    fn part2(ranges: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(ranges).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 24);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 93);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("498,4 -> 498,6\n503,4 -> 502,x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 14));
+        assert_eq!(error.expected, "a row");
+        let error = parse_input("1000000000,5 -> 1000000000,6").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        let error = parse_input("498,4 -> 498,499").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 14));
+    }
+}
@@ -0,0 +1,317 @@
+use std::{
+    cmp::{max, min},
+    collections::HashSet,
+    ops::RangeInclusive,
+    vec,
+};
+
+use crate::{
+    budget,
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::{Param, Params},
+    solution::{Answer, Solution},
+};
+
+mod reference;
+
+type Point = (i32, i32);
+
+type Entry = (Point, Point);
+
+/// Ranges of x, in `i64` since a sensor's reach can stretch past what an
+/// `i32` holds.
+struct Ranges {
+    ranges: Vec<RangeInclusive<i64>>,
+}
+
+fn overlap<T: Ord>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool {
+    a.start() <= b.end() && a.end() >= b.start()
+}
+
+fn union<T: Ord + Copy>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> RangeInclusive<T> {
+    *min(a.start(), b.start())..=*max(a.end(), b.end())
+}
+
+fn intersection<T: Ord + Copy>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> RangeInclusive<T> {
+    *max(a.start(), b.start())..=*min(a.end(), b.end())
+}
+
+fn difference(a: &RangeInclusive<i64>, b: &RangeInclusive<i64>) -> Vec<RangeInclusive<i64>> {
+    let mut result = vec![];
+
+    if b.start() > a.start() && b.start() <= a.end() {
+        result.push(*a.start()..=(*b.start() - 1));
+    }
+
+    if b.end() < a.end() && b.end() >= a.start() {
+        result.push((*b.end() + 1)..=*a.end());
+    }
+
+    result
+}
+
+impl Ranges {
+    fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    fn push(&mut self, value: RangeInclusive<i64>) {
+        let mut next_ranges = vec![];
+
+        let mut agg = value.clone();
+        for r in self.ranges.iter().cloned() {
+            if overlap(&r, &value) {
+                agg = union(&agg, &r);
+            } else {
+                next_ranges.push(r);
+            }
+        }
+        next_ranges.push(agg);
+
+        next_ranges.sort_by_key(|r| *r.start());
+
+        self.ranges = next_ranges;
+    }
+
+    fn clamp(&mut self, bounds: RangeInclusive<i64>) {
+        self.ranges = self
+            .ranges
+            .iter()
+            .filter(|r| overlap(r, &bounds))
+            .map(|r| intersection(r, &bounds))
+            .collect()
+    }
+
+    fn total_len(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|r| (r.end() - r.start()) as usize + 1)
+            .sum()
+    }
+}
+
+impl FromIterator<RangeInclusive<i64>> for Ranges {
+    fn from_iter<T: IntoIterator<Item = RangeInclusive<i64>>>(iter: T) -> Self {
+        let mut ranges = Self::new();
+        iter.into_iter().for_each(|r| ranges.push(r));
+        ranges
+    }
+}
+
+fn parse_coord(line: &Line, input: &str, axis: &str) -> Result<i32, ParseError> {
+    match input.split_once('=') {
+        Some((a, v)) if a == axis => line.parse(v.trim_end_matches([',', ':']), "a coordinate"),
+        _ => Err(line.error(input, &format!("`{}=`", axis))),
+    }
+}
+
+fn parse_entry(line: Line) -> Result<Entry, ParseError> {
+    match line.text.split_whitespace().collect::<Vec<&str>>()[..] {
+        ["Sensor", "at", s_x, s_y, "closest", "beacon", "is", "at", b_x, b_y] => Ok((
+            (parse_coord(&line, s_x, "x")?, parse_coord(&line, s_y, "y")?),
+            (parse_coord(&line, b_x, "x")?, parse_coord(&line, b_y, "y")?),
+        )),
+        _ => Err(line.mismatch("a sensor and its closest beacon")),
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Entry>, ParseError> {
+    lines(input).map(parse_entry).collect()
+}
+
+fn area_slice(&(sensor, beacon): &Entry, row: i32) -> Option<RangeInclusive<i64>> {
+    let distance = sensor.0.abs_diff(beacon.0) as i64 + sensor.1.abs_diff(beacon.1) as i64;
+    let offset = distance - sensor.1.abs_diff(row) as i64;
+
+    match offset {
+        0.. => Some((sensor.0 as i64 - offset)..=(sensor.0 as i64 + offset)),
+        _ => None,
+    }
+}
+
+fn process_part1(y: i32, entries: &[Entry]) -> usize {
+    let beacon_xs = entries
+        .iter()
+        .filter(|(_, (_, b_y))| *b_y == y)
+        .map(|&(_, (b_x, _))| b_x as i64)
+        .collect::<HashSet<_>>();
+
+    let slices = entries
+        .iter()
+        .filter_map(|e| area_slice(e, y))
+        .collect::<Ranges>();
+
+    slices.total_len()
+        - beacon_xs
+            .iter()
+            .filter(|x| slices.ranges.iter().any(|r| r.contains(x)))
+            .count()
+}
+
+/// The tuning frequency of the only position in the bounds no sensor
+/// rules out, if there is one and the budget lasts until it is found.
+fn process_part2(bound: i32, entries: &[Entry]) -> Option<i64> {
+    for y in 0..=bound {
+        if budget::spent() {
+            return None;
+        }
+
+        let beacon_xs = entries
+            .iter()
+            .filter(|(_, (_, b_y))| *b_y == y)
+            .map(|&(_, (b_x, _))| b_x as i64)
+            .collect::<HashSet<_>>();
+
+        let mut slices = entries
+            .iter()
+            .filter_map(|e| area_slice(e, y))
+            .chain(beacon_xs.iter().copied().map(|x| x..=x))
+            .collect::<Ranges>();
+
+        slices.clamp(0..=bound as i64);
+
+        if slices.total_len() == bound.try_into().unwrap() {
+            let positions = slices.ranges.iter().fold(vec![0..=bound as i64], |agg, next| {
+                agg.iter().flat_map(|r| difference(r, next)).collect()
+            });
+
+            if let [p] = &positions[..] {
+                return Some(*p.start() * 4000000 + y as i64);
+            }
+        }
+    }
+
+    None
+}
+
+const ROW: Param = Param {
+    name: "row",
+    default: 2000000,
+    range: i32::MIN as i64..=i32::MAX as i64,
+    help: "row part 1 counts the positions a beacon can't be in",
+};
+
+const BOUND: Param = Param {
+    name: "bound",
+    default: 4000000,
+    range: 0..=i32::MAX as i64,
+    help: "largest coordinate part 2 searches for the distress beacon",
+};
+
+/// `size` sensors plus four large ones placed diagonally around a random
+/// point, which between them cover everything within the bounds of part 2
+/// except that point, so the distress beacon has exactly one place to be.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    const BOUND: i64 = 4000000;
+
+    let hidden = (rng.range(0..=BOUND), rng.range(0..=BOUND));
+    let mut entries = [(1, 1), (1, -1), (-1, 1), (-1, -1)]
+        .map(|(dx, dy)| {
+            let sensor = (hidden.0 + dx * BOUND, hidden.1 + dy * BOUND);
+            (sensor, (hidden.0 - dx * (BOUND - 1), sensor.1))
+        })
+        .to_vec();
+
+    while entries.len() < size + 4 {
+        let sensor = (rng.range(0..=BOUND), rng.range(0..=BOUND));
+        let distance = sensor.0.abs_diff(hidden.0) + sensor.1.abs_diff(hidden.1);
+        if distance < 2 {
+            continue;
+        }
+        let reach = rng.range(1..=distance as i64 - 1);
+        let across = rng.range(0..=reach);
+        let beacon = (
+            sensor.0 + across * rng.pick(&[-1, 1]),
+            sensor.1 + (reach - across) * rng.pick(&[-1, 1]),
+        );
+        entries.push((sensor, beacon));
+    }
+    rng.shuffle(&mut entries);
+
+    entries
+        .iter()
+        .map(|((sx, sy), (bx, by))| {
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                sx, sy, bx, by
+            )
+        })
+        .collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 15;
+    const PARAMS: &'static [Param] = &[ROW, BOUND];
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a sensor report",
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<Entry>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(entries: &Self::Parsed, params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(params.get(&ROW), entries).into())
+    }
+
+    fn part2(entries: &Self::Parsed, params: &Params) -> Result<Option<Answer>, SolveError> {
+        match process_part2(params.get(&BOUND), entries) {
+            Some(frequency) => Ok(Some(frequency.into())),
+            None => Err(SolveError::no_answer("position for the distress beacon")),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn reference(part: u8, input: &str) -> Result<Option<String>, ParseError> {
+        Ok(match part {
+            1 => Some(reference::part1(2000000, input)?.to_string()),
+            2 => reference::part2(4000000, input)?.map(|n| n.to_string()),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(10, &parse_input(INPUT).unwrap());
+        assert_eq!(result, 26);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(20, &parse_input(INPUT).unwrap());
+        assert_eq!(result, Some(56000011));
+    }
+
+    #[test]
+    fn test_no_answer() {
+        let input = "Sensor at x=10, y=10: closest beacon is at x=40, y=10\n";
+        assert_eq!(process_part2(20, &parse_input(input).unwrap()), None);
+        assert_eq!(
+            Solver::part2(&parse_input(input).unwrap(), &Params::default()),
+            Err(SolveError::no_answer("position for the distress beacon"))
+        );
+    }
+
+    #[test]
+    fn test_reference() {
+        assert_eq!(reference::part1(10, INPUT), Ok(26));
+        assert_eq!(reference::part2(20, INPUT), Ok(Some(56000011)));
+    }
+}
@@ -0,0 +1,69 @@
+//! Checks positions one by one instead of merging ranges.
+
+use std::collections::HashSet;
+
+use super::{parse_input, Entry, ParseError};
+
+fn reach(&((sx, sy), (bx, by)): &Entry) -> i64 {
+    sx.abs_diff(bx) as i64 + sy.abs_diff(by) as i64
+}
+
+fn covered(entries: &[Entry], (x, y): (i64, i64)) -> bool {
+    entries.iter().any(|entry @ ((sx, sy), _)| {
+        (*sx as i64 - x).abs() + (*sy as i64 - y).abs() <= reach(entry)
+    })
+}
+
+/// Splits the row wherever a sensor's coverage starts or stops, and counts
+/// the stretches that some sensor covers.
+pub fn part1(row: i32, input: &str) -> Result<usize, ParseError> {
+    let entries = parse_input(input)?;
+    let mut cuts = entries
+        .iter()
+        .filter_map(|entry @ ((sx, sy), _)| {
+            let offset = reach(entry) - sy.abs_diff(row) as i64;
+            (offset >= 0).then_some([*sx as i64 - offset, *sx as i64 + offset + 1])
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+    cuts.sort();
+    cuts.dedup();
+
+    let covered_cells = cuts
+        .windows(2)
+        .filter(|pair| covered(&entries, (pair[0], row as i64)))
+        .map(|pair| (pair[1] - pair[0]) as usize)
+        .sum::<usize>();
+    let beacons = entries
+        .iter()
+        .map(|(_, beacon)| *beacon)
+        .filter(|&(_, y)| y == row)
+        .collect::<HashSet<_>>();
+
+    Ok(covered_cells - beacons.len())
+}
+
+/// The uncovered cell's neighbours are covered, so it lies just outside some
+/// sensor's reach: walks the cells one step beyond each sensor's reach.
+pub fn part2(bound: i32, input: &str) -> Result<Option<i64>, ParseError> {
+    let entries = parse_input(input)?;
+    let bound = bound as i64;
+
+    for entry @ ((sx, sy), _) in &entries {
+        let distance = reach(entry) + 1;
+        for dx in -distance..=distance {
+            let x = *sx as i64 + dx;
+            if !(0..=bound).contains(&x) {
+                continue;
+            }
+            let dy = distance - dx.abs();
+            for y in [*sy as i64 - dy, *sy as i64 + dy] {
+                if (0..=bound).contains(&y) && !covered(&entries, (x, y)) {
+                    return Ok(Some(x * 4000000 + y));
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,521 @@
+use std::{collections::HashMap, iter::once};
+
+use crate::{
+    budget,
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::{Param, Params},
+    repl::{Model, Query},
+    solution::{Answer, Solution},
+};
+
+type ValveKey = [char; 2];
+
+const START: ValveKey = ['A', 'A'];
+
+const PART1_MINUTES: Param = Param {
+    name: "part1-minutes",
+    default: 30,
+    range: 0..=100,
+    help: "minutes before the volcano erupts when working alone",
+};
+
+const PART2_MINUTES: Param = Param {
+    name: "part2-minutes",
+    default: 26,
+    range: 0..=100,
+    help: "minutes left after teaching the elephant",
+};
+
+#[derive(Clone)]
+pub struct Valve {
+    key: ValveKey,
+    flow: usize,
+    tunnels: Vec<ValveKey>,
+}
+
+impl Valve {
+    fn new(key: ValveKey, flow: usize, tunnels: Vec<ValveKey>) -> Self {
+        Self { key, flow, tunnels }
+    }
+}
+
+fn parse_key(line: &Line, input: &str) -> Result<ValveKey, ParseError> {
+    match input.chars().collect::<Vec<char>>()[..] {
+        [a, b] if a.is_ascii_uppercase() && b.is_ascii_uppercase() => Ok([a, b]),
+        _ => Err(line.error(input, "a valve name like `AA`")),
+    }
+}
+
+fn parse_line(line: &Line) -> Result<Valve, ParseError> {
+    let rest = line
+        .text
+        .strip_prefix("Valve ")
+        .ok_or_else(|| line.mismatch("`Valve `"))?;
+    let (key, rest) = rest
+        .split_once(" has flow rate=")
+        .ok_or_else(|| line.error(rest, "a valve name and its flow rate"))?;
+    let (flow, rest) = rest
+        .split_once("; ")
+        .ok_or_else(|| line.error(rest, "a flow rate followed by `; `"))?;
+    let tunnels = ["tunnels lead to valves ", "tunnel leads to valve "]
+        .iter()
+        .find_map(|prefix| rest.strip_prefix(prefix))
+        .ok_or_else(|| line.error(rest, "the valves its tunnels lead to"))?;
+
+    Ok(Valve::new(
+        parse_key(line, key)?,
+        line.parse(flow, "a flow rate")?,
+        tunnels
+            .split(", ")
+            .map(|k| parse_key(line, k))
+            .collect::<Result<_, _>>()?,
+    ))
+}
+
+fn parse_input(input: &str) -> Result<HashMap<ValveKey, Valve>, ParseError> {
+    let valves = lines(input)
+        .map(|line| Ok((line, parse_line(&line)?)))
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    let valve_map = valves
+        .iter()
+        .map(|(_, v)| (v.key, v.clone()))
+        .collect::<HashMap<ValveKey, Valve>>();
+
+    for (line, valve) in &valves {
+        if let Some(key) = valve.tunnels.iter().find(|k| !valve_map.contains_key(*k)) {
+            let name = key.iter().collect::<String>();
+            let at = line.text.rfind(&name).map_or(line.text, |i| &line.text[i..]);
+            return Err(line.error(at, "a tunnel to a listed valve"));
+        }
+    }
+    if !valve_map.contains_key(&START) {
+        return Err(ParseError::end_of_input(valves.len(), "valve AA"));
+    }
+
+    Ok(valve_map)
+}
+
+fn distance(
+    from_key: &ValveKey,
+    to_key: &ValveKey,
+    visited: &[ValveKey],
+    valves: &HashMap<ValveKey, Valve>,
+) -> Option<usize> {
+    let from = valves.get(from_key).unwrap();
+    if from.tunnels.contains(to_key) {
+        Some(1)
+    } else {
+        let next_visited = visited
+            .iter()
+            .chain(once(from_key))
+            .copied()
+            .collect::<Vec<ValveKey>>();
+        from.tunnels
+            .iter()
+            .filter(|k| !visited.contains(k))
+            .filter_map(|k| distance(k, to_key, &next_visited, valves))
+            .min()
+            .map(|d| d + 1)
+    }
+}
+
+/// Minutes between AA and the working valves, and between working valves.
+/// Valves no tunnels lead to are left out, as they can never be opened.
+fn build_distances(valves: &HashMap<ValveKey, Valve>) -> HashMap<(ValveKey, ValveKey), usize> {
+    let keys = valves
+        .iter()
+        .filter(|(k, v)| v.flow > 0 || **k == START)
+        .map(|(k, _)| k)
+        .cloned()
+        .collect::<Vec<ValveKey>>();
+
+    keys.iter()
+        .flat_map(|a| keys.iter().copied().map(|b| (*a, b)))
+        .filter(|(a, b)| a < b)
+        .flat_map(|(a, b)| {
+            let Some(d) = distance(&a, &b, &[], valves) else {
+                return vec![];
+            };
+            if a == START {
+                vec![((a, b), d)]
+            } else {
+                vec![((a, b), d), ((b, a), d)]
+            }
+        })
+        .collect()
+}
+
+/// Every walk from `last` to valves worth opening in time, or just `last` if
+/// none are; once the budget is spent, only those found so far.
+fn walks(
+    countdown: usize,
+    last: ValveKey,
+    distances: &HashMap<(ValveKey, ValveKey), usize>,
+    include_partial: bool,
+) -> Vec<Vec<ValveKey>> {
+    if countdown <= 2 || distances.is_empty() || budget::spent() {
+        return vec![vec![last]];
+    }
+
+    let result = distances
+        .iter()
+        .filter(|((k, _), d)| k == &last && **d < countdown - 1)
+        .flat_map(|((_, next), d)| {
+            let next_countdown = countdown - *d - 1;
+            let mut result = walks(
+                next_countdown,
+                *next,
+                &distances
+                    .iter()
+                    .filter(|((_, k), _)| k != next)
+                    .map(|(k, v)| (*k, *v))
+                    .collect(),
+                include_partial,
+            )
+            .iter()
+            .map(|visits| once(&last).chain(visits.iter()).cloned().collect())
+            .collect::<Vec<_>>();
+
+            if include_partial {
+                result.push(vec![last]);
+            };
+
+            result
+        })
+        .collect::<Vec<_>>();
+
+    if result.is_empty() {
+        vec![vec![last]]
+    } else {
+        result
+    }
+}
+
+fn score(
+    countdown: usize,
+    walk: &[ValveKey],
+    distances: &HashMap<(ValveKey, ValveKey), usize>,
+    valves: &HashMap<ValveKey, Valve>,
+) -> usize {
+    if walk.len() <= 1 {
+        0
+    } else {
+        let (head, rest) = walk.split_first().unwrap();
+        let next = rest.first().unwrap();
+        let next_countdown = countdown - distances.get(&(*head, *next)).unwrap() - 1;
+        valves.get(next).unwrap().flow * next_countdown
+            + score(next_countdown, rest, distances, valves)
+    }
+}
+
+fn process_part1(minutes: usize, valve_map: &HashMap<ValveKey, Valve>) -> usize {
+    let distances = build_distances(valve_map);
+
+    walks(minutes, START, &distances, false)
+        .iter()
+        .map(|w| score(minutes, w, &distances, valve_map))
+        .max()
+        .unwrap()
+}
+
+fn pairs<T: 'static + Clone>(input: &[T]) -> impl Iterator<Item = (T, T)> + '_ {
+    (0..input.len()).flat_map(move |i| {
+        ((i + 1)..input.len())
+            .map(move |j| (input.get(i).unwrap().clone(), input.get(j).unwrap().clone()))
+    })
+}
+
+fn process_part2(minutes: usize, valve_map: &HashMap<ValveKey, Valve>) -> usize {
+    let distances = build_distances(valve_map);
+
+    let walks = walks(minutes, START, &distances, true)
+        .iter()
+        .filter(|w| w.len() > 1)
+        .map(|w| (w.clone(), score(minutes, w, &distances, valve_map)))
+        .collect::<Vec<_>>();
+
+    pairs(&walks)
+        .take_while(|_| !budget::spent())
+        .filter(|((a, _), (b, _))| {
+            !a.iter()
+                .filter(|item_a| item_a != &&START)
+                .any(|item_a| b.contains(item_a))
+        })
+        .map(|((_, score_a), (_, score_b))| score_a + score_b)
+        .max()
+        .unwrap_or(0)
+}
+
+/// The valves and the distances between those worth visiting, for `aoc repl`.
+struct Explorer {
+    valves: HashMap<ValveKey, Valve>,
+    distances: HashMap<(ValveKey, ValveKey), usize>,
+}
+
+impl Explorer {
+    fn key(&self, name: &str) -> Result<ValveKey, String> {
+        match name.chars().collect::<Vec<_>>()[..] {
+            [a, b] if [a, b] == START || self.valves.get(&[a, b]).is_some_and(|v| v.flow > 0) => {
+                Ok([a, b])
+            }
+            _ => Err(format!("`{}` is neither AA nor a working valve", name)),
+        }
+    }
+
+    /// `None` if no tunnels lead from one to the other.
+    fn distance(&self, from: ValveKey, to: ValveKey) -> Option<usize> {
+        match self.distances.get(&(from, to)) {
+            _ if from == to => Some(0),
+            Some(d) => Some(*d),
+            None => self.distances.get(&(to, from)).copied(),
+        }
+    }
+
+    /// Working valves, in name order.
+    fn working(&self) -> Vec<&Valve> {
+        let mut result = self.valves.values().filter(|v| v.flow > 0).collect::<Vec<_>>();
+        result.sort_by_key(|v| v.key);
+        result
+    }
+}
+
+impl Model for Explorer {
+    fn queries(&self) -> &'static [Query] {
+        &[
+            Query {
+                name: "valves",
+                args: &[],
+                help: "the working valves and their flow rates",
+            },
+            Query {
+                name: "distance",
+                args: &["FROM", "TO"],
+                help: "minutes from one valve to another, both AA or working",
+            },
+            Query {
+                name: "distances",
+                args: &["FROM"],
+                help: "minutes from a valve to each working valve",
+            },
+        ]
+    }
+
+    fn query(&self, name: &str, args: &[&str]) -> Result<String, String> {
+        let name_of = |key: &ValveKey| key.iter().collect::<String>();
+        Ok(match name {
+            "valves" => self
+                .working()
+                .iter()
+                .map(|v| format!("{} {}", name_of(&v.key), v.flow))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "distance" => self
+                .distance(self.key(args[0])?, self.key(args[1])?)
+                .ok_or_else(|| format!("no tunnels lead from {} to {}", args[0], args[1]))?
+                .to_string(),
+            _ => {
+                let from = self.key(args[0])?;
+                let minutes = |to| {
+                    self.distance(from, to)
+                        .map_or("-".to_string(), |d| d.to_string())
+                };
+                self.working()
+                    .iter()
+                    .map(|v| format!("{} {}", name_of(&v.key), minutes(v.key)))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        })
+    }
+}
+
+/// `size` valves, at least three, joined into a tree with a few extra
+/// tunnels. About a quarter of them work, and always at least two, so the
+/// elephant has a valve of its own to open in part 2.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let count = size.clamp(3, 26 * 26);
+
+    let mut names = vec!["AA".to_string()];
+    while names.len() < count {
+        let name = [rng.below(26), rng.below(26)]
+            .map(|i| (b'A' + i as u8) as char)
+            .iter()
+            .collect::<String>();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    rng.shuffle(&mut names);
+
+    let mut tunnels = vec![vec![]; count];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for i in 1..count {
+        connect(i, rng.below(i));
+    }
+    for _ in 0..count / 10 {
+        connect(rng.below(count), rng.below(count));
+    }
+
+    let start = names.iter().position(|name| name == "AA").unwrap();
+    let mut flows = (0..count)
+        .map(|_| {
+            if rng.chance(1, 4) {
+                rng.range(1..=25)
+            } else {
+                0
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut others = (0..count).filter(|&i| i != start).collect::<Vec<_>>();
+    rng.shuffle(&mut others);
+    for &i in &others[..2] {
+        flows[i] = rng.range(1..=25);
+    }
+    flows[start] = 0;
+
+    (0..count)
+        .map(|i| {
+            let targets = tunnels[i]
+                .iter()
+                .map(|&t| names[t].as_str())
+                .collect::<Vec<_>>();
+            let tunnels = match targets.len() {
+                1 => "tunnel leads to valve",
+                _ => "tunnels lead to valves",
+            };
+            format!(
+                "Valve {} has flow rate={}; {} {}\n",
+                names[i],
+                flows[i],
+                tunnels,
+                targets.join(", ")
+            )
+        })
+        .collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 16;
+    const PARAMS: &'static [Param] = &[PART1_MINUTES, PART2_MINUTES];
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a valve scan",
+        ..Section::TEXT
+    }];
+
+    type Parsed = HashMap<ValveKey, Valve>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(valves: &Self::Parsed, params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(params.get(&PART1_MINUTES), valves).into())
+    }
+
+    fn part2(valves: &Self::Parsed, params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(params.get(&PART2_MINUTES), valves).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn model(valves: &Self::Parsed, _params: &Params) -> Option<Box<dyn Model>> {
+        let distances = build_distances(valves);
+        Some(Box::new(Explorer {
+            valves: valves.clone(),
+            distances,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{budget::Budget, solution::Day};
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(30, &parse_input(INPUT).unwrap());
+        assert_eq!(result, 1651);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(26, &parse_input(INPUT).unwrap());
+        assert_eq!(result, 1707);
+    }
+
+    #[test]
+    fn test_few_minutes() {
+        // Nothing is worth opening in 2 minutes, and only BB or DD in 3.
+        let valves = parse_input(INPUT).unwrap();
+        assert_eq!([0, 2, 3].map(|minutes| process_part1(minutes, &valves)), [0, 0, 20]);
+        assert_eq!([0, 2, 3].map(|minutes| process_part2(minutes, &valves)), [0, 0, 33]);
+    }
+
+    #[test]
+    fn test_timed_out() {
+        let day = Day::of::<Solver>();
+        let valves = day.parse(INPUT).unwrap();
+        let budget = Budget::unlimited();
+        budget.cancel();
+        assert_eq!(
+            day.solve_within(1, &valves, &Params::default(), &budget),
+            Err(SolveError::TimedOut {
+                day: 16,
+                part: 1,
+                best: Some(Answer::Integer(0))
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB, CC\n\
+                     Valve BB has flow rate=13; tunnel leads to valve AA";
+        let error = parse_input(input).err().unwrap();
+        assert_eq!((error.line, error.column), (1, 54));
+        assert_eq!(error.expected, "a tunnel to a listed valve");
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let input = "Valve AA has flow rate=0; tunnel leads to valve BB\n\
+                     Valve BB has flow rate=10; tunnel leads to valve AA\n\
+                     Valve CC has flow rate=20; tunnel leads to valve DD\n\
+                     Valve DD has flow rate=0; tunnel leads to valve CC";
+        let valves = parse_input(input).unwrap();
+        assert_eq!(process_part1(30, &valves), 280);
+
+        let distances = build_distances(&valves);
+        let explorer = Explorer { valves, distances };
+        assert_eq!(explorer.query("distances", &["AA"]), Ok("BB 1\nCC -".to_string()));
+        assert!(explorer.query("distance", &["AA", "CC"]).is_err());
+    }
+
+    #[test]
+    fn test_queries() {
+        let valves = parse_input(INPUT).unwrap();
+        let distances = build_distances(&valves);
+        let explorer = Explorer { valves, distances };
+        assert_eq!(explorer.query("distance", &["AA", "HH"]), Ok("5".to_string()));
+        assert_eq!(explorer.query("distance", &["JJ", "BB"]), Ok("3".to_string()));
+        assert_eq!(
+            explorer.query("distances", &["AA"]),
+            Ok("BB 1\nCC 2\nDD 1\nEE 2\nHH 5\nJJ 2".to_string())
+        );
+        assert!(explorer.query("distance", &["AA", "FF"]).is_err());
+    }
+}
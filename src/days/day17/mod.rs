@@ -0,0 +1,328 @@
+use std::{collections::HashMap, iter::{once, repeat, repeat_n}, ops::Range};
+
+use crate::{
+    error::{lines, ParseError, SolveError},
+    generate::Rng,
+    grid::Grid,
+    lint::Section,
+    params::{Param, Params},
+    solution::{Answer, Solution},
+    trace,
+};
+
+mod reference;
+
+#[derive(Clone)]
+pub enum Dir {
+    Left,
+    Right,
+}
+
+const WIDTH: usize = 7;
+
+const BOUNDS: Range<i32> = 0..WIDTH as i32;
+
+/// How many of the top rows stand for the whole stack when looking for a
+/// cycle. 16 was enough for puzzle inputs, but `aoc diff` found short jet
+/// patterns whose rocks fall deeper than that.
+const MAGIC: usize = 64;
+
+type Stack = Grid<bool>;
+
+type Point = (usize, usize);
+
+type State = (usize, usize, Vec<bool>);
+
+const START_X: usize = 2;
+
+const PART1_ROCKS: Param = Param {
+    name: "part1-rocks",
+    default: 2022,
+    range: 0..=i64::MAX,
+    help: "rocks to drop in part 1",
+};
+
+const PART2_ROCKS: Param = Param {
+    name: "part2-rocks",
+    default: 1000000000000,
+    range: 0..=i64::MAX,
+    help: "rocks to drop in part 2",
+};
+
+struct Shape {
+    points: Vec<Point>,
+}
+
+impl Shape {
+    fn new(pattern: Vec<Point>, bottom_y: usize) -> Self {
+        let points = pattern
+            .iter()
+            .map(|(x, y)| (x + START_X, y + bottom_y))
+            .collect();
+        Self { points }
+    }
+
+    fn flat(bottom_y: usize) -> Self {
+        Self::new(vec![(0, 0), (1, 0), (2, 0), (3, 0)], bottom_y)
+    }
+
+    fn plus(bottom_y: usize) -> Self {
+        Self::new(vec![(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)], bottom_y)
+    }
+
+    fn j(bottom_y: usize) -> Self {
+        Self::new(vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)], bottom_y)
+    }
+
+    fn i(bottom_y: usize) -> Self {
+        Self::new(vec![(0, 0), (0, 1), (0, 2), (0, 3)], bottom_y)
+    }
+
+    fn square(bottom_y: usize) -> Self {
+        Self::new(vec![(0, 0), (0, 1), (1, 0), (1, 1)], bottom_y)
+    }
+
+    fn shift(&mut self, dir: &Dir, stack: &Stack) {
+        let offset = match dir {
+            Dir::Left => -1,
+            Dir::Right => 1,
+        };
+
+        let next_points = self
+            .points
+            .iter()
+            .map(|(x, y)| {
+                let next_x = *x as i32 + offset;
+                if BOUNDS.contains(&next_x) && !stack[(next_x as usize, *y)] {
+                    Some((next_x.try_into().unwrap(), *y))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if next_points.iter().all(|np| np.is_some()) {
+            self.points = next_points.iter().filter_map(|np| *np).collect();
+        }
+    }
+
+    fn fall(&mut self, stack: &Stack) -> Option<()> {
+        let next_points = self
+            .points
+            .iter()
+            .map(|(x, y)| {
+                if *y > 0 && !stack[(*x, y - 1)] {
+                    Some((*x, y - 1))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if next_points.iter().all(|np| np.is_some()) {
+            self.points = next_points.iter().filter_map(|np| *np).collect();
+            Some(())
+        } else {
+            None
+        }
+    }
+}
+
+fn pad(source: &mut Stack) {
+    repeat_n(vec![false; WIDTH], 7).for_each(|row| source.push_row(row));
+}
+
+fn trim(source: &mut Stack) {
+    if !source.row(source.height() - 1).iter().any(|x| *x) {
+        source.pop_row();
+        trim(source);
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Dir>, ParseError> {
+    let mut lines = lines(input.trim_end());
+    let line = lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(0, "a jet pattern"))?;
+    if let Some(extra) = lines.next() {
+        return Err(extra.mismatch("a single line of jets"));
+    }
+
+    line.text
+        .char_indices()
+        .map(|(i, c)| match c {
+            '<' => Ok(Dir::Left),
+            '>' => Ok(Dir::Right),
+            _ => Err(line.error(&line.text[i..i + c.len_utf8()], "`<` or `>`")),
+        })
+        .collect()
+}
+
+/// Draws the top rows of the stack, the ones cycles are looked for in.
+fn format(stack: &Stack) -> String {
+    (0..stack.height())
+        .rev()
+        .take(MAGIC)
+        .map(|y| {
+            let row = stack.row(y).iter().map(|&c| if c { '#' } else { '.' });
+            format!("|{}|", row.collect::<String>())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn generate_shapes() -> impl Iterator<Item = (usize, &'static fn(usize) -> Shape)> {
+    repeat(
+        [Shape::flat, Shape::plus, Shape::j, Shape::i, Shape::square]
+            .iter()
+            .enumerate(),
+    )
+    .flatten()
+}
+
+fn process(n: usize, jets: &[Dir]) -> usize {
+    let mut directions = repeat(jets.iter().cloned().enumerate().collect::<Vec<_>>()).flatten();
+    let mut shapes = generate_shapes();
+
+    let mut stack = Grid::new(WIDTH, 0, false);
+
+    let mut state_set = HashMap::<State, (usize, usize)>::new();
+    let mut loop_adjustment = None;
+
+    let mut i = 0;
+    while i < n {
+        let bottom_y = stack.height() + 3;
+
+        pad(&mut stack);
+
+        let (shape_idx, shape_fn) = shapes.next().unwrap();
+        let mut shape = shape_fn(bottom_y);
+
+        let mut direction_idx = 0;
+
+        directions
+            .by_ref()
+            .map(|(dir_idx, dir)| {
+                direction_idx = dir_idx;
+                shape.shift(&dir, &stack);
+                shape.fall(&stack)
+            })
+            .take_while(|x| x.is_some())
+            .for_each(drop);
+
+        for (x, y) in shape.points {
+            stack[(x, y)] = true;
+        }
+
+        trim(&mut stack);
+        trace!(Steps, "rock {} rests, height {}", i + 1, stack.height());
+        trace!(States, "{}\n", format(&stack));
+
+        if loop_adjustment.is_none() && stack.height() >= MAGIC {
+            let state: State = (
+                shape_idx,
+                direction_idx,
+                stack.row_span((stack.height() - MAGIC)..stack.height()).to_vec(),
+            );
+
+            if let Some((prev_i, prev_len)) = state_set.get(&state) {
+                let jump_i = (n - 1) - ((n - 1 - prev_i) % (i - prev_i));
+                trace!(
+                    Steps,
+                    "rocks {} and {} leave the same top, skipping to rock {}",
+                    prev_i + 1,
+                    i + 1,
+                    jump_i + 1
+                );
+                loop_adjustment = Some((stack.height() - prev_len) * ((jump_i - i) / (i - prev_i)));
+                i = jump_i;
+            }
+            state_set.insert(state, (i, stack.height()));
+        }
+
+        i += 1;
+    }
+
+    stack.height() + loop_adjustment.unwrap_or_default()
+}
+
+/// A jet pattern `size` jets long.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| *rng.pick(&['<', '>']))
+        .chain(once('\n'))
+        .collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 17;
+    const PARAMS: &'static [Param] = &[PART1_ROCKS, PART2_ROCKS];
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "the jet pattern",
+        allowed: "`<` or `>`",
+        chars: |c| c == '<' || c == '>',
+        count: 1..=1,
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<Dir>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(jets: &Self::Parsed, params: &Params) -> Result<Answer, SolveError> {
+        Ok(process(params.get(&PART1_ROCKS), jets).into())
+    }
+
+    fn part2(jets: &Self::Parsed, params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process(params.get(&PART2_ROCKS), jets).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn reference(part: u8, input: &str) -> Result<Option<String>, ParseError> {
+        Ok(match part {
+            1 => Some(reference::part1(input)?.to_string()),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    fn height(n: usize, input: &str) -> usize {
+        process(n, &parse_input(input).unwrap())
+    }
+
+    #[test]
+    fn test_part1() {
+        let result = height(2022, INPUT);
+        assert_eq!(result, 3068);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = height(1000000000000, INPUT);
+        assert_eq!(result, 1514285714288);
+    }
+
+    #[test]
+    fn test_reference() {
+        assert_eq!(reference::part1(INPUT), Ok(3068));
+    }
+
+    #[test]
+    fn test_whole_cycles() {
+        let result = height(2022, ">><");
+        assert_eq!(result, 4044);
+    }
+}
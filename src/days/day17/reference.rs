@@ -0,0 +1,52 @@
+//! Drops every rock, without skipping ahead over repeating stretches.
+
+use std::collections::HashSet;
+
+use super::{parse_input, Dir, ParseError, WIDTH};
+
+/// Each rock's cells, from its bottom left corner.
+const ROCKS: [&[(usize, usize)]; 5] = [
+    &[(0, 0), (1, 0), (2, 0), (3, 0)],
+    &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+    &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+    &[(0, 0), (0, 1), (0, 2), (0, 3)],
+    &[(0, 0), (1, 0), (0, 1), (1, 1)],
+];
+
+fn fits(filled: &HashSet<(usize, usize)>, rock: &[(usize, usize)], x: usize, y: usize) -> bool {
+    rock.iter()
+        .all(|(dx, dy)| x + dx < WIDTH && !filled.contains(&(x + dx, y + dy)))
+}
+
+fn tower_height(rocks: usize, jets: &[Dir]) -> usize {
+    let mut filled = HashSet::new();
+    let mut height = 0;
+    let mut jet = jets.iter().cycle();
+
+    for rock in ROCKS.iter().cycle().take(rocks) {
+        let (mut x, mut y) = (2, height + 3);
+        loop {
+            match jet.next().unwrap() {
+                Dir::Left if x > 0 && fits(&filled, rock, x - 1, y) => x -= 1,
+                Dir::Right if fits(&filled, rock, x + 1, y) => x += 1,
+                _ => {}
+            }
+            if y > 0 && fits(&filled, rock, x, y - 1) {
+                y -= 1;
+            } else {
+                break;
+            }
+        }
+
+        for (dx, dy) in rock.iter() {
+            filled.insert((x + dx, y + dy));
+            height = height.max(y + dy + 1);
+        }
+    }
+
+    height
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    Ok(tower_height(2022, &parse_input(input)?))
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+};
+
+type Cube = (isize, isize, isize);
+
+#[derive(PartialEq)]
+enum Cell {
+    Lava,
+    Water,
+}
+
+/// A cube, whose coordinates fit in an `i32` so the air around it can't
+/// overflow an `isize`.
+fn parse_cube(line: Line) -> Result<Cube, ParseError> {
+    let coordinate = |at| line.parse::<i32>(at, "a coordinate that fits in 32 bits");
+    match line.text.split(',').collect::<Vec<&str>>()[..] {
+        [x, y, z] => Ok((
+            coordinate(x)? as isize,
+            coordinate(y)? as isize,
+            coordinate(z)? as isize,
+        )),
+        _ => Err(line.mismatch("a cube like `2,2,2`")),
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Cube>, ParseError> {
+    let cubes = lines(input).map(parse_cube).collect::<Result<Vec<_>, _>>()?;
+    if cubes.is_empty() {
+        return Err(ParseError::end_of_input(0, "a cube"));
+    }
+    Ok(cubes)
+}
+
+fn neighbors((x, y, z): &Cube) -> [Cube; 6] {
+    [
+        (x - 1, *y, *z),
+        (x + 1, *y, *z),
+        (*x, y - 1, *z),
+        (*x, y + 1, *z),
+        (*x, *y, z - 1),
+        (*x, *y, z + 1),
+    ]
+}
+
+fn process_part1(source: &[Cube]) -> usize {
+    let cubes = source
+        .iter()
+        .map(|cube| (*cube, Some(())))
+        .collect::<HashMap<Cube, Option<()>>>();
+
+    cubes
+        .keys()
+        .map(|cube| 6 - neighbors(cube).iter().filter_map(|c| cubes.get(c)).count())
+        .sum()
+}
+
+fn flood_fill(cubes: &mut HashMap<Cube, Cell>, point: &Cube, min: &Cube, max: &Cube) {
+    if point.0 < min.0 || point.1 < min.1 || point.2 < min.2 {
+        return;
+    }
+    if point.0 > max.0 || point.1 > max.1 || point.2 > max.2 {
+        return;
+    }
+    if !cubes.contains_key(point) {
+        cubes.insert(*point, Cell::Water);
+        neighbors(point)
+            .iter()
+            .for_each(|p| flood_fill(cubes, p, min, max))
+    }
+}
+
+fn process_part2(source: &[Cube]) -> usize {
+    let mut cubes = source
+        .iter()
+        .map(|cube| (*cube, Cell::Lava))
+        .collect::<HashMap<Cube, Cell>>();
+
+    let min_x = source.iter().map(|&(x, _, _)| x).min().unwrap();
+    let max_x = source.iter().map(|&(x, _, _)| x).max().unwrap();
+    let min_y = source.iter().map(|&(_, y, _)| y).min().unwrap();
+    let max_y = source.iter().map(|&(_, y, _)| y).max().unwrap();
+    let min_z = source.iter().map(|&(_, _, z)| z).min().unwrap();
+    let max_z = source.iter().map(|&(_, _, z)| z).max().unwrap();
+
+    let min = &(min_x - 1, min_y - 1, min_z - 1);
+    let max = &(max_x + 1, max_y + 1, max_z + 1);
+
+    flood_fill(&mut cubes, &(min_x - 1, min_y, min_z), min, max);
+    flood_fill(&mut cubes, &(max_x + 1, min_y, min_z), min, max);
+    flood_fill(&mut cubes, &(min_x, min_y - 1, min_z), min, max);
+    flood_fill(&mut cubes, &(min_x, max_y + 1, min_z), min, max);
+    flood_fill(&mut cubes, &(min_x, min_y, min_z - 1), min, max);
+    flood_fill(&mut cubes, &(min_x, min_y, max_z + 1), min, max);
+
+    cubes
+        .iter()
+        .filter(|(_, v)| **v == Cell::Lava)
+        .map(|(p, _)| {
+            neighbors(p)
+                .iter()
+                .filter(|c| cubes.get(c) == Some(&Cell::Water))
+                .count()
+        })
+        .sum()
+}
+
+/// `size` distinct cubes, filling about a third of a box around them, which
+/// leaves pockets of air inside the droplet.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let count = size.max(1);
+    let side = ((count * 3) as f64).cbrt().ceil() as usize;
+
+    let mut filled = vec![false; side * side * side];
+    let mut result = String::new();
+    for _ in 0..count {
+        let cube = loop {
+            let cube = rng.below(filled.len());
+            if !filled[cube] {
+                break cube;
+            }
+        };
+        filled[cube] = true;
+        result += &format!(
+            "{},{},{}\n",
+            cube % side,
+            cube / side % side,
+            cube / side / side
+        );
+    }
+    result
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 18;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a cube",
+        allowed: "a digit, `-` or `,`",
+        chars: |c| c.is_ascii_digit() || c == '-' || c == ',',
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<Cube>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(cubes: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(cubes).into())
+    }
+
+    fn part2(cubes: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(cubes).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 64);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 58);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("1,1,1\n1,2147483648,1\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(process_part1(&parse_input("2147483647,0,-2147483648\n").unwrap()), 6);
+    }
+}
@@ -0,0 +1,340 @@
+use std::{
+    cmp::max,
+    collections::HashMap,
+    ops::{Add, Sub},
+};
+
+use crate::{
+    budget,
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::{Param, Params},
+    pool,
+    repl::{Model, Query},
+    solution::{Answer, Solution},
+};
+
+mod reference;
+
+const PART1_MINUTES: Param = Param {
+    name: "part1-minutes",
+    default: 24,
+    range: 0..=40,
+    help: "minutes to crack geodes in for the quality levels",
+};
+
+const PART2_MINUTES: Param = Param {
+    name: "part2-minutes",
+    default: 32,
+    range: 0..=40,
+    help: "minutes to crack geodes in with the first three blueprints",
+};
+
+type Resources = (usize, usize, usize);
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Bot {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+type Bots = [(Bot, usize); 4];
+type Blueprint = [(Option<Bot>, Resources); 5];
+
+fn zip<T>(
+    &(aa, ab, ac): &Resources,
+    &(ba, bb, bc): &Resources,
+    f: fn(usize, usize) -> T,
+) -> (T, T, T) {
+    (f(aa, ba), f(ab, bb), f(ac, bc))
+}
+
+fn le((aa, ab, ac): &Resources, (ba, bb, bc): &Resources) -> bool {
+    aa <= ba && ab <= bb && ac <= bc
+}
+
+fn sub(a: &Resources, b: &Resources) -> Resources {
+    zip(a, b, Sub::sub)
+}
+
+fn add(a: &Resources, b: &Resources) -> Resources {
+    zip(a, b, Add::add)
+}
+
+fn to_tuple<T: Copy>(&source: &[T; 3]) -> (T, T, T) {
+    (source[0], source[1], source[2])
+}
+
+fn triangle(value: usize) -> usize {
+    if value == 0 {
+        return 0;
+    }
+    value + triangle(value - 1)
+}
+
+fn process_internal(
+    blueprint: &Blueprint,
+    bots: &Bots,
+    resources: &Resources,
+    time: usize,
+    memo: &mut HashMap<(Bots, Resources, usize), usize>,
+    max_geodes: usize,
+    magic: usize,
+) -> usize {
+    if bots[3].1 * time + triangle(time) <= max_geodes || budget::spent() {
+        return 0;
+    }
+    if time == 0 {
+        0
+    } else if time == 1 {
+        bots[3].1
+    } else if let Some(result) = memo.get(&(*bots, *resources, time)) {
+        *result
+    } else {
+        let next_resources = add(
+            resources,
+            &to_tuple(&bots.map(|(_, n)| n)[..3].try_into().unwrap()),
+        );
+
+        let mut moves = blueprint
+            .iter()
+            .rev()
+            .filter(|(_, cost)| le(cost, resources))
+            .collect::<Vec<_>>();
+
+        if let Some(geode_move) = moves.iter().find(|(b, _)| b == &Some(Bot::Geode)) {
+            moves = vec![geode_move];
+        } else if moves
+            .iter()
+            .filter_map(|(b, _)| *b)
+            .filter(|b| [Bot::Ore, Bot::Clay].contains(b))
+            .count()
+            == 2
+        {
+            moves.retain(|(b, _)| b.is_some());
+        };
+
+        let result = bots[3].1
+            + moves.iter().fold(0, |agg, (bot, cost)| {
+                let next_bots =
+                    bots.map(|(b, n)| if bot == &Some(b) { (b, n + 1) } else { (b, n) });
+                max(
+                    agg,
+                    process_internal(
+                        blueprint,
+                        &next_bots,
+                        &sub(&next_resources, cost),
+                        time - 1,
+                        memo,
+                        max_geodes,
+                        magic,
+                    ),
+                )
+            });
+
+        if time >= magic {
+            memo.insert((*bots, *resources, time), result);
+        }
+
+        result
+    }
+}
+
+/// The most geodes `blueprint` can crack in `time` minutes. Once the budget
+/// is spent, the plans not yet tried count for nothing, leaving the most
+/// found so far.
+fn process(blueprint: &Blueprint, time: usize, magic: usize) -> usize {
+    process_internal(
+        blueprint,
+        &[
+            (Bot::Ore, 1),
+            (Bot::Clay, 0),
+            (Bot::Obsidian, 0),
+            (Bot::Geode, 0),
+        ],
+        &(0, 0, 0),
+        time,
+        &mut HashMap::<_, _>::new(),
+        0,
+        magic,
+    )
+}
+
+fn parse_line(line: Line) -> Result<(usize, Blueprint), ParseError> {
+    let cost = |s| line.parse(s, "a cost");
+    match line.text.split_whitespace().collect::<Vec<&str>>()[..] {
+        [
+            "Blueprint", i,
+            "Each", "ore", "robot", "costs", oo, "ore.",
+            "Each", "clay", "robot", "costs", co, "ore.",
+            "Each", "obsidian", "robot", "costs", obo, "ore", "and", obc, "clay.",
+            "Each", "geode", "robot", "costs", go, "ore", "and", gob, "obsidian.",
+        ] => Ok((
+            line.parse(i.trim_end_matches(':'), "a blueprint number")?,
+            [
+                (None, (0, 0, 0)),
+                (Some(Bot::Ore), (cost(oo)?, 0, 0)),
+                (Some(Bot::Clay), (cost(co)?, 0, 0)),
+                (Some(Bot::Obsidian), (cost(obo)?, cost(obc)?, 0)),
+                (Some(Bot::Geode), (cost(go)?, 0, cost(gob)?)),
+            ],
+        )),
+        _ => Err(line.mismatch("a blueprint listing the cost of each robot")),
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<(usize, Blueprint)>, ParseError> {
+    lines(input).map(parse_line).collect()
+}
+
+fn process_part1(minutes: usize, blueprints: &[(usize, Blueprint)]) -> usize {
+    pool::map(blueprints, |(i, b)| i * process(b, minutes, 3))
+        .iter()
+        .sum()
+}
+
+fn process_part2(minutes: usize, blueprints: &[(usize, Blueprint)]) -> usize {
+    let first = &blueprints[..blueprints.len().min(3)];
+    pool::map(first, |(_, b)| process(b, minutes, 5))
+        .iter()
+        .product()
+}
+
+/// The blueprints, for `aoc repl`.
+struct Explorer {
+    blueprints: Vec<(usize, Blueprint)>,
+}
+
+impl Model for Explorer {
+    fn queries(&self) -> &'static [Query] {
+        &[Query {
+            name: "best",
+            args: &["ID", "[MINUTES]"],
+            help: "most geodes a blueprint can open in MINUTES (24)",
+        }]
+    }
+
+    fn query(&self, _name: &str, args: &[&str]) -> Result<String, String> {
+        let blueprint = args[0]
+            .parse::<usize>()
+            .ok()
+            .and_then(|id| self.blueprints.iter().find(|(i, _)| *i == id))
+            .map(|(_, b)| b)
+            .ok_or_else(|| format!("no blueprint `{}`", args[0]))?;
+        let minutes = match args.get(1) {
+            Some(minutes) => match minutes.parse() {
+                Ok(minutes) if minutes <= 40 => minutes,
+                _ => return Err("MINUTES should be a number up to 40".to_string()),
+            },
+            None => PART1_MINUTES.default as usize,
+        };
+        // Memoising the last few minutes as well pays off on longer runs, as
+        // in the parts.
+        let magic = if minutes > 24 { 5 } else { 3 };
+        Ok(process(blueprint, minutes, magic).to_string())
+    }
+}
+
+/// `size` blueprints, with costs in the ranges of the puzzle's own.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (1..=size.max(1))
+        .map(|i| {
+            format!(
+                "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+                 Each obsidian robot costs {} ore and {} clay. \
+                 Each geode robot costs {} ore and {} obsidian.\n",
+                i,
+                rng.range(2..=4),
+                rng.range(2..=4),
+                rng.range(2..=4),
+                rng.range(4..=20),
+                rng.range(2..=4),
+                rng.range(7..=20)
+            )
+        })
+        .collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 19;
+    const PARAMS: &'static [Param] = &[PART1_MINUTES, PART2_MINUTES];
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a blueprint",
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<(usize, Blueprint)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(blueprints: &Self::Parsed, params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(params.get(&PART1_MINUTES), blueprints).into())
+    }
+
+    fn part2(blueprints: &Self::Parsed, params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(params.get(&PART2_MINUTES), blueprints).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn reference(part: u8, input: &str) -> Result<Option<String>, ParseError> {
+        Ok(match part {
+            1 => Some(reference::part1(input)?.to_string()),
+            2 => Some(reference::part2(input)?.to_string()),
+            _ => None,
+        })
+    }
+
+    fn model(blueprints: &Self::Parsed, _params: &Params) -> Option<Box<dyn Model>> {
+        Some(Box::new(Explorer {
+            blueprints: blueprints.clone(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(24, &parse_input(INPUT).unwrap());
+        assert_eq!(result, 33);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replacen("costs 2 ore.", "costs two ore.", 1);
+        let error = parse_input(&input).err().unwrap();
+        assert_eq!((error.line, error.column), (1, 64));
+        assert_eq!(error.found, "`two`");
+    }
+
+    #[test]
+    fn test_reference() {
+        assert_eq!(reference::part1(INPUT), Ok(33));
+        assert_eq!(reference::part2(INPUT), Ok(56 * 62));
+    }
+
+    #[test]
+    fn test_queries() {
+        let explorer = Explorer {
+            blueprints: parse_input(INPUT).unwrap(),
+        };
+        assert_eq!(explorer.query("best", &["1"]), Ok("9".to_string()));
+        assert_eq!(explorer.query("best", &["2", "24"]), Ok("12".to_string()));
+        assert!(explorer.query("best", &["3"]).is_err());
+        assert!(explorer.query("best", &["1", "41"]).is_err());
+    }
+}
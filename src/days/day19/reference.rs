@@ -0,0 +1,91 @@
+//! Searches every order of building robots, without the pruning heuristics.
+
+use super::{parse_input, Blueprint, Bot, ParseError};
+
+struct Search {
+    /// What each robot costs, as ore, clay and obsidian.
+    costs: Vec<(Bot, [usize; 3])>,
+    best: usize,
+}
+
+impl Search {
+    /// Tries each robot as the next one to build. A geode robot is credited
+    /// up front with every geode it will open; other robots stop being built
+    /// once there are as many as their resource could ever be spent in one
+    /// minute.
+    fn run(&mut self, time: usize, bots: [usize; 3], stock: [usize; 3], geodes: usize) {
+        self.best = self.best.max(geodes);
+        // Even a geode robot every minute from now on couldn't do better.
+        if geodes + time * time.saturating_sub(1) / 2 <= self.best {
+            return;
+        }
+
+        for i in 0..self.costs.len() {
+            let (bot, cost) = self.costs[i];
+            let kind = match bot {
+                Bot::Ore => Some(0),
+                Bot::Clay => Some(1),
+                Bot::Obsidian => Some(2),
+                Bot::Geode => None,
+            };
+            if let Some(kind) = kind {
+                if self.costs.iter().all(|(_, c)| bots[kind] >= c[kind]) {
+                    continue;
+                }
+            }
+
+            let Some(wait) = (0..3)
+                .map(|i| match (cost[i].saturating_sub(stock[i]), bots[i]) {
+                    (0, _) => Some(0),
+                    (_, 0) => None,
+                    (missing, n) => Some(missing.div_ceil(n)),
+                })
+                .try_fold(0, |a, b| b.map(|b| a.max(b)))
+            else {
+                continue;
+            };
+            if wait + 1 >= time {
+                continue;
+            }
+
+            let left = time - wait - 1;
+            let stock = [0, 1, 2].map(|i| stock[i] + bots[i] * (wait + 1) - cost[i]);
+            match kind {
+                Some(kind) => {
+                    let mut bots = bots;
+                    bots[kind] += 1;
+                    self.run(left, bots, stock, geodes);
+                }
+                None => self.run(left, bots, stock, geodes + left),
+            }
+        }
+    }
+}
+
+/// The most geodes opened in `time` minutes.
+fn geodes(blueprint: &Blueprint, time: usize) -> usize {
+    let mut search = Search {
+        costs: blueprint[1..]
+            .iter()
+            .map(|&(bot, (ore, clay, obsidian))| (bot.unwrap(), [ore, clay, obsidian]))
+            .collect(),
+        best: 0,
+    };
+    search.run(time, [1, 0, 0], [0; 3], 0);
+    search.best
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    Ok(parse_input(input)?
+        .iter()
+        .map(|(i, b)| i * geodes(b, 24))
+        .sum())
+}
+
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    Ok(parse_input(input)?
+        .iter()
+        .take(3)
+        .map(|(_, b)| geodes(b, 32))
+        .product())
+}
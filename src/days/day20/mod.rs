@@ -0,0 +1,151 @@
+use crate::{
+    error::{lines, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+};
+
+fn parse_input(input: &str) -> Result<Vec<(usize, isize)>, ParseError> {
+    let ns = lines(input)
+        .map(|line| line.parse::<isize>(line.text, "a number"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // The grove coordinates are counted from the zero.
+    if !ns.contains(&0) {
+        return Err(ParseError::end_of_input(ns.len(), "a 0 somewhere in the file"));
+    }
+    Ok(ns.into_iter().enumerate().collect())
+}
+
+fn mix(input: Vec<(usize, isize)>) -> Vec<(usize, isize)> {
+    let mut ns = input;
+
+    let len = ns.len();
+    // A lone number has nowhere to move to.
+    if len < 2 {
+        return ns;
+    }
+
+    for i in 0..len {
+        let index = ns
+            .iter()
+            .enumerate()
+            .find(|(_, (id, _))| id == &i)
+            .unwrap()
+            .0;
+
+        let item = ns.remove(index);
+        // Reduced first, so adding the index can't overflow.
+        let steps = item.1.rem_euclid(len as isize - 1);
+        let next_index = (index as isize + steps)
+            .rem_euclid(len as isize - 1)
+            .try_into()
+            .unwrap();
+        ns.insert(next_index, item);
+    }
+
+    ns
+}
+
+/// The sum of the grove coordinates, or `None` if it doesn't fit in an
+/// `isize`.
+fn score(ns: &[isize]) -> Option<isize> {
+    let start = ns.iter().enumerate().find(|(_, v)| **v == 0).unwrap().0;
+
+    [1000, 2000, 3000]
+        .iter()
+        .map(|i| (i + start) % ns.len())
+        .try_fold(0, |sum: isize, i| sum.checked_add(ns[i]))
+}
+
+fn process_part1(ns: &[(usize, isize)]) -> Option<isize> {
+    let mixed = mix(ns.to_vec());
+
+    score(&mixed.iter().map(|(_, v)| *v).collect::<Vec<_>>())
+}
+
+/// As part 1 with the decryption key applied, or `None` if a decrypted
+/// number or the sum doesn't fit in an `isize`.
+fn process_part2(ns: &[(usize, isize)]) -> Option<isize> {
+    let ns = ns
+        .iter()
+        .map(|&(i, v)| Some((i, v.checked_mul(811589153)?)))
+        .collect::<Option<_>>()?;
+    let mixed = (0..10).fold(ns, |agg, _| mix(agg));
+
+    score(&mixed.iter().map(|(_, v)| *v).collect::<Vec<_>>())
+}
+
+/// `size` numbers, exactly one of which is 0.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut numbers = (1..size.max(1))
+        .map(|_| rng.range(1..=10000) * rng.pick(&[-1, 1]))
+        .collect::<Vec<_>>();
+    numbers.insert(rng.below(numbers.len() + 1), 0);
+    numbers.iter().map(|n| format!("{}\n", n)).collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 20;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a number",
+        allowed: "a digit or `-`",
+        chars: |c| c.is_ascii_digit() || c == '-',
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<(usize, isize)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(ns: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        match process_part1(ns) {
+            Some(sum) => Ok(sum.into()),
+            None => Err(SolveError::no_answer("sum that fits in an `isize`")),
+        }
+    }
+
+    fn part2(ns: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        match process_part2(ns) {
+            Some(sum) => Ok(Some(sum.into())),
+            None => Err(SolveError::no_answer("decrypted sum that fits in an `isize`")),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, Some(3));
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(result, Some(1623178306));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let ns = parse_input("0\n20000000000000\n5\n").unwrap();
+        assert_eq!(process_part1(&ns), Some(20000000000005));
+        assert_eq!(process_part2(&ns), None);
+        let ns = parse_input(&format!("0\n{}\n{}\n", isize::MAX, isize::MAX)).unwrap();
+        assert_eq!(process_part1(&ns), None);
+    }
+}
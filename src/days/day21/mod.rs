@@ -0,0 +1,539 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::{self, Rng},
+    lint::Section,
+    params::Params,
+    repl::{Model, Query},
+    solution::{Answer, Solution},
+};
+
+type MonkeyKey = [char; 4];
+
+#[derive(Clone, Copy)]
+pub enum Monkey {
+    Const(usize),
+    Op(MonkeyKey, MonkeyKey, Op),
+    Var,
+}
+
+#[derive(Clone, Copy)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// `None` where the result doesn't fit in a `usize`, or for a division by 0.
+fn op(a: usize, b: usize, f: &Op) -> Option<usize> {
+    (match f {
+        Op::Add => usize::checked_add,
+        Op::Sub => usize::checked_sub,
+        Op::Mul => usize::checked_mul,
+        Op::Div => usize::checked_div,
+    })(a, b)
+}
+
+const ROOT: MonkeyKey = ['r', 'o', 'o', 't'];
+
+fn parse_op(line: &Line, input: &str) -> Result<Op, ParseError> {
+    match input {
+        "+" => Ok(Op::Add),
+        "-" => Ok(Op::Sub),
+        "*" => Ok(Op::Mul),
+        "/" => Ok(Op::Div),
+        _ => Err(line.error(input, "one of + - * /")),
+    }
+}
+
+fn parse_key(line: &Line, input: &str) -> Result<MonkeyKey, ParseError> {
+    input
+        .chars()
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| line.error(input, "a four letter monkey name"))
+}
+
+fn parse_value(line: &Line, input: &str) -> Result<Monkey, ParseError> {
+    match input.split_whitespace().collect::<Vec<_>>()[..] {
+        [v] => Ok(Monkey::Const(line.parse(v, "a number")?)),
+        [a, op, b] => Ok(Monkey::Op(
+            parse_key(line, a)?,
+            parse_key(line, b)?,
+            parse_op(line, op)?,
+        )),
+        _ => Err(line.error(input, "a number or an operation like `aaaa + bbbb`")),
+    }
+}
+
+fn parse_line(line: &Line) -> Result<(MonkeyKey, Monkey), ParseError> {
+    match line.text.split_once(':') {
+        Some((key, value)) => Ok((parse_key(line, key)?, parse_value(line, value.trim_start())?)),
+        None => Err(line.mismatch("a monkey like `root: pppw + sjmn`")),
+    }
+}
+
+/// The first monkey, in input order, that waits for itself, directly or
+/// through others; working out what it yells would never end. Monkeys are
+/// worked out as soon as all they wait for is, and the ones left over are in
+/// a loop or wait for one.
+fn find_loop<'a>(parsed: &'a [(Line, (MonkeyKey, Monkey))]) -> Option<&'a Line<'a>> {
+    let mut waiting = HashMap::new();
+    let mut waiters = HashMap::<MonkeyKey, Vec<MonkeyKey>>::new();
+    let mut ready = vec![];
+    for (_, (key, monkey)) in parsed {
+        match monkey {
+            Monkey::Op(a, b, _) => {
+                waiting.insert(*key, 2);
+                waiters.entry(*a).or_default().push(*key);
+                waiters.entry(*b).or_default().push(*key);
+            }
+            _ => ready.push(*key),
+        }
+    }
+    while let Some(key) = ready.pop() {
+        for waiter in waiters.remove(&key).unwrap_or_default() {
+            let count = waiting.get_mut(&waiter).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                ready.push(waiter);
+            }
+        }
+    }
+
+    parsed
+        .iter()
+        .find(|(_, (key, _))| waiting.get(key).is_some_and(|&count| count > 0))
+        .map(|(line, _)| line)
+}
+
+fn parse_input(input: &str) -> Result<HashMap<MonkeyKey, Monkey>, ParseError> {
+    let parsed = lines(input)
+        .map(|line| Ok((line, parse_line(&line)?)))
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    let mut listed = HashSet::new();
+    for (line, (key, _)) in &parsed {
+        if !listed.insert(key) {
+            let name = key.iter().collect::<String>();
+            return Err(line.error(&line.text[..name.len()], "a monkey not listed before"));
+        }
+    }
+    let monkeys = parsed
+        .iter()
+        .map(|(_, entry)| *entry)
+        .collect::<HashMap<MonkeyKey, Monkey>>();
+
+    for (line, (_, monkey)) in &parsed {
+        if let Monkey::Op(a, b, _) = monkey {
+            if let Some(key) = [a, b].into_iter().find(|k| !monkeys.contains_key(*k)) {
+                let name = key.iter().collect::<String>();
+                let at = line.text.rfind(&name).map_or(line.text, |i| &line.text[i..]);
+                return Err(line.error(at, "a listed monkey"));
+            }
+        }
+    }
+    if !monkeys.contains_key(&ROOT) {
+        return Err(ParseError::end_of_input(parsed.len(), "the root monkey"));
+    }
+    if let Some(line) = find_loop(&parsed) {
+        return Err(line.mismatch("a monkey that doesn't wait for itself"));
+    }
+
+    Ok(monkeys)
+}
+
+/// The number `key` yells, or `None` if it depends on `humn` in part 2 or
+/// can't be worked out within a `usize`.
+fn yell(key: &MonkeyKey, monkeys: &HashMap<MonkeyKey, Monkey>) -> Option<usize> {
+    let monkey = monkeys.get(key).unwrap();
+
+    match monkey {
+        Monkey::Const(v) => Some(*v),
+        Monkey::Op(a, b, f) => op(yell(a, monkeys)?, yell(b, monkeys)?, f),
+        Monkey::Var => None,
+    }
+}
+
+fn contains_var(key: &MonkeyKey, monkeys: &HashMap<MonkeyKey, Monkey>) -> bool {
+    match monkeys.get(key).unwrap() {
+        Monkey::Const(_) => false,
+        Monkey::Var => true,
+        Monkey::Op(a, b, _) => contains_var(a, monkeys) || contains_var(b, monkeys),
+    }
+}
+
+fn flip_op(source: &Op) -> Op {
+    match source {
+        Op::Add => Op::Sub,
+        Op::Sub => Op::Add,
+        Op::Mul => Op::Div,
+        Op::Div => Op::Mul,
+    }
+}
+
+fn flip(
+    source: &Monkey,
+    new_subkey: &MonkeyKey,
+    monkeys: &HashMap<MonkeyKey, Monkey>,
+) -> (Monkey, MonkeyKey) {
+    match source {
+        Monkey::Op(a, b, f) if contains_var(a, monkeys) => {
+            (Monkey::Op(*new_subkey, *b, flip_op(f)), *a)
+        }
+        Monkey::Op(a, b, f) if contains_var(b, monkeys) => {
+            if matches!(f, Op::Div | Op::Sub) {
+                (Monkey::Op(*b, *new_subkey, flip_op(f)), *a)
+            } else {
+                (Monkey::Op(*new_subkey, *a, flip_op(f)), *b)
+            }
+        }
+        _ => panic!(),
+    }
+}
+
+/// What `humn` yells for `left` and `right` to yell the same, or `None` if
+/// it isn't on exactly one side, or the number doesn't fit in a `usize`.
+fn balance(
+    left: &MonkeyKey,
+    right: &MonkeyKey,
+    monkeys: &HashMap<MonkeyKey, Monkey>,
+) -> Option<usize> {
+    match [left, right].map(|k| monkeys.get(k).unwrap()) {
+        [Monkey::Var, _] => yell(right, monkeys),
+        [_, Monkey::Var] => yell(left, monkeys),
+        [l, _] if contains_var(left, monkeys) => {
+            let (flipped, out) = flip(l, right, monkeys);
+            balance(
+                &out,
+                left,
+                &monkeys
+                    .iter()
+                    .map(|(k, v)| if k == left { (*k, flipped) } else { (*k, *v) })
+                    .collect(),
+            )
+        }
+        [_, r] if contains_var(right, monkeys) => {
+            let (flipped, out) = flip(r, left, monkeys);
+            balance(
+                right,
+                &out,
+                &monkeys
+                    .iter()
+                    .map(|(k, v)| if k == right { (*k, flipped) } else { (*k, *v) })
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+fn process_part1(monkeys: &HashMap<MonkeyKey, Monkey>) -> Option<usize> {
+    yell(&ROOT, monkeys)
+}
+
+fn process_part2(monkeys: &HashMap<MonkeyKey, Monkey>) -> Option<usize> {
+    let mut monkeys = monkeys.clone();
+
+    let humn = ['h', 'u', 'm', 'n'];
+    monkeys.remove(&humn);
+    monkeys.insert(humn, Monkey::Var);
+
+    match monkeys.get(&ROOT).unwrap() {
+        Monkey::Op(left, right, _) => balance(left, right, &monkeys),
+        _ => None,
+    }
+}
+
+/// The monkeys' jobs, for `aoc repl`.
+struct Explorer {
+    monkeys: HashMap<MonkeyKey, Monkey>,
+}
+
+impl Explorer {
+    fn key(&self, name: &str) -> Result<MonkeyKey, String> {
+        name.chars()
+            .collect::<Vec<_>>()
+            .try_into()
+            .ok()
+            .filter(|key| self.monkeys.contains_key(key))
+            .ok_or_else(|| format!("no monkey `{}`", name))
+    }
+
+    /// `key`'s job with the monkeys it waits for written out `depth` deep;
+    /// deeper ones are left as names.
+    fn expression(&self, key: &MonkeyKey, depth: usize) -> String {
+        match self.monkeys[key] {
+            _ if depth == 0 => key.iter().collect(),
+            Monkey::Const(v) => v.to_string(),
+            Monkey::Op(a, b, f) => {
+                let symbol = match f {
+                    Op::Add => '+',
+                    Op::Sub => '-',
+                    Op::Mul => '*',
+                    Op::Div => '/',
+                };
+                let term = |k| match self.monkeys[k] {
+                    Monkey::Op(..) if depth > 1 => format!("({})", self.expression(k, depth - 1)),
+                    _ => self.expression(k, depth - 1),
+                };
+                format!("{} {} {}", term(&a), symbol, term(&b))
+            }
+            Monkey::Var => "x".to_string(),
+        }
+    }
+}
+
+impl Model for Explorer {
+    fn queries(&self) -> &'static [Query] {
+        &[
+            Query {
+                name: "yell",
+                args: &["KEY"],
+                help: "the number a monkey yells",
+            },
+            Query {
+                name: "expr",
+                args: &["KEY", "[DEPTH]"],
+                help: "a monkey's job, with the jobs it waits for DEPTH (1) deep",
+            },
+        ]
+    }
+
+    fn query(&self, name: &str, args: &[&str]) -> Result<String, String> {
+        let key = self.key(args[0])?;
+        match name {
+            "yell" => yell(&key, &self.monkeys)
+                .map(|number| number.to_string())
+                .ok_or_else(|| format!("`{}` yells a number that doesn't fit", args[0])),
+            _ => {
+                let depth = match args.get(1) {
+                    Some(depth) => depth.parse().map_err(|_| "DEPTH should be a number")?,
+                    None => 1,
+                };
+                Ok(self.expression(&key, depth))
+            }
+        }
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Builds generated monkeys, keeping their names unique.
+struct Troop<'a> {
+    rng: &'a mut Rng,
+    names: HashSet<String>,
+    lines: Vec<String>,
+}
+
+impl Troop<'_> {
+    fn name(&mut self) -> String {
+        loop {
+            let name = generate::name(self.rng, 4);
+            if self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    /// A monkey yelling `value`, worked out by up to `budget` operations
+    /// that all divide evenly and never go below zero.
+    fn constant(&mut self, value: usize, budget: usize) -> String {
+        let name = self.name();
+        if budget == 0 {
+            self.lines.push(format!("{}: {}", name, value));
+            return name;
+        }
+
+        let divisor = (2..=9).find(|d| value > 0 && value.is_multiple_of(*d));
+        let (a, op, b) = match (self.rng.below(4), divisor) {
+            (0, Some(d)) => (d, '*', value / d),
+            (1, _) if value < 1_000_000_000_000 => {
+                let b = self.rng.range(2..=5) as usize;
+                (value * b, '/', b)
+            }
+            (2, _) => {
+                let b = self.rng.range(0..=20) as usize;
+                (value + b, '-', b)
+            }
+            _ => {
+                let a = self.rng.range(0..=value as i64) as usize;
+                (a, '+', value - a)
+            }
+        };
+        let left = self.rng.below(budget);
+        let a = self.constant(a, left);
+        let b = self.constant(b, budget - 1 - left);
+        self.lines.push(format!("{}: {} {} {}", name, a, op, b));
+        name
+    }
+}
+
+/// About `size` monkeys. `humn` sits at the bottom of a chain of about a
+/// quarter of them, whose operations stay exact both for the number `humn`
+/// yells in part 1 and for the one that balances `root` in part 2.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut troop = Troop {
+        rng,
+        names: HashSet::from(["root".to_string(), "humn".to_string()]),
+        lines: vec![],
+    };
+
+    let part1 = troop.rng.range(1..=1000) as usize;
+    let part2 = troop.rng.range(1..=1_000_000) as usize;
+    troop.lines.push(format!("humn: {}", part1));
+
+    let mut chain = "humn".to_string();
+    let (mut value1, mut value2) = (part1, part2);
+    let mut scale = 1;
+    for _ in 0..(size / 4).max(1) {
+        let divisor = [2, 3, 5, 7]
+            .into_iter()
+            .find(|d| gcd(value1, value2).is_multiple_of(*d));
+        let multiplier = troop.rng.range(2..=9) as usize;
+        let (op, operand) = match (troop.rng.below(4), divisor) {
+            (0, Some(d)) => ('/', d),
+            (1, _) if scale * multiplier <= 1_000_000 => ('*', multiplier),
+            (2, _) => {
+                let most = value1.min(value2).min(1000);
+                ('-', troop.rng.range(0..=most as i64) as usize)
+            }
+            _ => ('+', troop.rng.range(0..=1000) as usize),
+        };
+        (value1, value2, scale) = match op {
+            '/' => (value1 / operand, value2 / operand, (scale / operand).max(1)),
+            '*' => (value1 * operand, value2 * operand, scale * operand),
+            '-' => (value1 - operand, value2 - operand, scale),
+            _ => (value1 + operand, value2 + operand, scale),
+        };
+
+        let budget = troop.rng.below(3);
+        let operand = troop.constant(operand, budget);
+        let name = troop.name();
+        // Part 2 only solves for `humn` on the right of `+` and `*`.
+        if matches!(op, '+' | '*') && troop.rng.chance(1, 2) {
+            troop
+                .lines
+                .push(format!("{}: {} {} {}", name, operand, op, chain));
+        } else {
+            troop
+                .lines
+                .push(format!("{}: {} {} {}", name, chain, op, operand));
+        }
+        chain = name;
+    }
+
+    let other = troop.constant(value2, size / 2);
+    troop.lines.push(format!("root: {} + {}", chain, other));
+
+    let mut lines = troop.lines;
+    rng.shuffle(&mut lines);
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 21;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a monkey's job",
+        ..Section::TEXT
+    }];
+
+    type Parsed = HashMap<MonkeyKey, Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(monkeys: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        match process_part1(monkeys) {
+            Some(number) => Ok(number.into()),
+            None => Err(SolveError::no_answer("number for root within a `usize`")),
+        }
+    }
+
+    fn part2(monkeys: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        match process_part2(monkeys) {
+            Some(number) => Ok(Some(number.into())),
+            None => Err(SolveError::no_answer("number for humn that balances root")),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn model(monkeys: &Self::Parsed, _params: &Params) -> Option<Box<dyn Model>> {
+        Some(Box::new(Explorer {
+            monkeys: monkeys.clone(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, Some(152));
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(result, Some(301));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("root: pppw % sjmn\npppw: 1\nsjmn: 2").err().unwrap();
+        assert_eq!((error.line, error.column), (1, 12));
+        assert_eq!(error.expected, "one of + - * /");
+
+        let looped = "root: aaaa + cccc\naaaa: bbbb * cccc\nbbbb: aaaa - cccc\ncccc: 2";
+        let error = parse_input(looped).err().unwrap();
+        assert_eq!(
+            (error.line, error.expected.as_str()),
+            (1, "a monkey that doesn't wait for itself")
+        );
+        let error = parse_input("root: 1\nroot: 2").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn test_no_answer() {
+        let monkeys = parse_input("root: 5\nhumn: 1").unwrap();
+        assert_eq!(process_part1(&monkeys), Some(5));
+        assert_eq!(process_part2(&monkeys), None);
+        let both_sides = parse_input("root: humn + humn\nhumn: 1").unwrap();
+        assert_eq!(process_part2(&both_sides), None);
+        let below_zero = parse_input("root: humn - aaaa\nhumn: 1\naaaa: 2").unwrap();
+        assert_eq!(process_part1(&below_zero), None);
+    }
+
+    #[test]
+    fn test_queries() {
+        let explorer = Explorer {
+            monkeys: parse_input(INPUT).unwrap(),
+        };
+        assert_eq!(explorer.query("yell", &["sjmn"]), Ok("150".to_string()));
+        assert_eq!(explorer.query("expr", &["root"]), Ok("pppw + sjmn".to_string()));
+        assert_eq!(
+            explorer.query("expr", &["root", "3"]),
+            Ok("((sllz + lgvd) / 4) + ((hmdt - zczc) * 5)".to_string())
+        );
+        assert!(explorer.query("yell", &["nope"]).is_err());
+    }
+}
@@ -0,0 +1,425 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    grid::{Grid, ORTHOGONAL},
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+};
+
+type Board = Grid<Option<bool>>;
+
+#[derive(Debug)]
+pub enum Dir {
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+pub enum Path {
+    Move(usize),
+    Turn(Dir),
+}
+
+type Point = (usize, usize);
+
+type Basis = (isize, isize);
+
+/// The board and the path to follow on it.
+type Notes = (Board, Vec<Path>);
+
+fn parse_line(line: Line) -> Result<Vec<Option<bool>>, ParseError> {
+    line.text
+        .char_indices()
+        .map(|(i, c)| match c {
+            '.' => Ok(Some(true)),
+            '#' => Ok(Some(false)),
+            ' ' => Ok(None),
+            _ => Err(line.error(&line.text[i..i + c.len_utf8()], "one of `.`, `#` or a space")),
+        })
+        .collect()
+}
+
+fn parse_path(line: Line) -> Result<Vec<Path>, ParseError> {
+    let mut digit_start = None;
+    let mut result = vec![];
+    for (i, c) in line.text.char_indices() {
+        if c.is_ascii_digit() {
+            digit_start.get_or_insert(i);
+        } else {
+            if let Some(start) = digit_start.take() {
+                result.push(Path::Move(line.parse(&line.text[start..i], "a distance")?));
+            }
+            match c {
+                'L' => result.push(Path::Turn(Dir::Left)),
+                'R' => result.push(Path::Turn(Dir::Right)),
+                _ => {
+                    let at = &line.text[i..i + c.len_utf8()];
+                    return Err(line.error(at, "a distance, `L` or `R`"));
+                }
+            }
+        }
+    }
+    if let Some(start) = digit_start {
+        result.push(Path::Move(line.parse(&line.text[start..], "a distance")?));
+    }
+
+    Ok(result)
+}
+
+fn parse_input(input: &str) -> Result<Notes, ParseError> {
+    let mut lines = lines(input);
+    let rows = lines
+        .by_ref()
+        .take_while(|l| !l.text.is_empty())
+        .map(parse_line)
+        .collect::<Result<Vec<_>, _>>()?;
+    let board = Grid::from_rows(rows, None);
+
+    if !board.rows().next().is_some_and(|row| row.contains(&Some(true))) {
+        return Err(ParseError::new(1, 1, "an open tile on the first row", "none"));
+    }
+
+    let path = lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input.lines().count(), "a path"))?;
+    Ok((board, parse_path(path)?))
+}
+
+fn mv(grid: &Board, (x, y): Point, facing: Basis, n: usize) -> Point {
+    let mut slice = match facing {
+        (-1 | 1, 0) => grid
+            .row(y)
+            .iter()
+            .enumerate()
+            .map(|(x, v)| ((x, y), v))
+            .collect::<Vec<_>>(),
+        (0, -1 | 1) => grid
+            .column(x)
+            .enumerate()
+            .map(|(y, v)| ((x, y), v))
+            .collect(),
+        _ => panic!(),
+    };
+
+    if matches!(facing, (-1 | 0, -1 | 0)) {
+        slice.reverse();
+    }
+
+    slice.retain(|(_, c)| c.is_some());
+
+    let result = slice
+        .iter()
+        .cycle()
+        .skip_while(|(p, _)| p != &(x, y))
+        .take_while(|(_, c)| c.unwrap_or_default())
+        .take(n + 1)
+        .last()
+        .unwrap()
+        .0;
+
+    result
+}
+
+fn turn((x, y): Basis, dir: &Dir) -> Basis {
+    match dir {
+        Dir::Left => (y, -x),
+        Dir::Right => (-y, x),
+    }
+}
+
+fn walk(grid: &Board, coord: Point, facing: Basis, path: &Path) -> (Point, Basis) {
+    match path {
+        Path::Move(n) => (mv(grid, coord, facing, *n), facing),
+        Path::Turn(dir) => (coord, turn(facing, dir)),
+    }
+}
+
+fn score((col, row): &Point, facing: &Basis) -> usize {
+    (row + 1) * 1000
+        + (col + 1) * 4
+        + match facing {
+            (1, 0) => 0,
+            (0, 1) => 1,
+            (-1, 0) => 2,
+            (0, -1) => 3,
+            _ => panic!(),
+        }
+}
+
+fn process_part1((grid, path): &Notes) -> usize {
+    let start = (
+        grid.row(0)
+            .iter()
+            .enumerate()
+            .find(|(_, &c)| c == Some(true))
+            .unwrap()
+            .0,
+        0,
+    );
+
+    let (pos, facing) = path.iter().fold((start, (1, 0)), |(coord, facing), p| {
+        walk(grid, coord, facing, p)
+    });
+
+    score(&pos, &facing)
+}
+
+type Node = HashMap<Basis, (Point, Basis)>;
+
+fn opp((x, y): &Basis) -> Basis {
+    (-x, -y)
+}
+
+struct GraphIterator<'a> {
+    graph: &'a HashMap<Point, Node>,
+    pos: Point,
+    facing: Basis,
+}
+
+impl GraphIterator<'_> {
+    fn new<'a>(
+        graph: &'a HashMap<Point, Node>,
+        &pos: &Point,
+        &facing: &Basis,
+    ) -> GraphIterator<'a> {
+        GraphIterator { graph, pos, facing }
+    }
+}
+
+impl Iterator for GraphIterator<'_> {
+    type Item = (Point, Basis);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.graph.get(&self.pos).unwrap().get(&self.facing);
+
+        if let Some((pos, facing)) = result {
+            self.pos = *pos;
+            self.facing = *facing;
+        }
+
+        result.copied()
+    }
+}
+
+struct BiRange {
+    to: usize,
+    cur: Option<usize>,
+    asc: bool,
+}
+
+impl BiRange {
+    fn new(from: usize, to: usize) -> Self {
+        Self {
+            cur: Some(from),
+            to,
+            asc: from <= to,
+        }
+    }
+}
+
+impl Iterator for BiRange {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(cur) = self.cur {
+            if self.asc {
+                let next = cur + 1;
+                self.cur = if next > self.to { None } else { Some(next) };
+                Some(cur)
+            } else {
+                let next = if cur == 0 { None } else { Some(cur - 1) };
+                self.cur = next.filter(|n| n >= &self.to);
+                Some(cur)
+            }
+        } else {
+            None
+        }
+    }
+}
+
+fn point_range(&(ax, ay): &Point, &(bx, by): &Point) -> impl Iterator<Item = Point> {
+    BiRange::new(ax, bx).flat_map(move |x| BiRange::new(ay, by).map(move |y| (x, y)))
+}
+
+type Stitch = (Point, Point, Basis, Point, Point, Basis);
+
+/// How the edges of the puzzle input's cube net are glued together.
+const STITCHES: [Stitch; 7] = [
+    ((50, 0), (99, 0), (0, -1), (0, 150), (0, 199), (-1, 0)),
+    ((100, 0), (149, 0), (0, -1), (0, 199), (49, 199), (0, 1)),
+    ((149, 0), (149, 49), (1, 0), (99, 149), (99, 100), (1, 0)),
+    ((50, 0), (50, 49), (-1, 0), (0, 149), (0, 100), (-1, 0)),
+    ((100, 49), (149, 49), (0, 1), (99, 50), (99, 99), (1, 0)),
+    ((50, 50), (50, 99), (-1, 0), (0, 100), (49, 100), (0, -1)),
+    ((50, 149), (99, 149), (0, 1), (49, 150), (49, 199), (1, 0)),
+];
+
+fn process_part2((grid, path): &Notes, stitches: &[Stitch]) -> usize {
+    let mut nodes = grid
+        .iter()
+        .filter(|(_, cell)| cell.unwrap_or_default())
+        .map(|(p, _)| (p, HashMap::<_, _>::new()))
+        .collect::<HashMap<_, _>>();
+
+    for (p, edges) in nodes.iter_mut() {
+        for b in ORTHOGONAL {
+            if let Some(next) = grid.step(*p, b) {
+                if grid[next].unwrap_or_default() {
+                    edges.insert(b, (next, b));
+                }
+            }
+        }
+    }
+
+    for (from_a, from_b, from_edge, to_a, to_b, to_edge) in stitches {
+        let from = point_range(from_a, from_b);
+        let to = point_range(to_a, to_b);
+
+        for (f, t) in from.zip(to) {
+            if nodes.contains_key(&f) && nodes.contains_key(&t) {
+                let mut from_edges = nodes.remove(&f).unwrap();
+                let mut to_edges = nodes.remove(&t).unwrap();
+                from_edges.insert(*from_edge, (t, opp(to_edge)));
+                to_edges.insert(*to_edge, (f, opp(from_edge)));
+                nodes.insert(f, from_edges);
+                nodes.insert(t, to_edges);
+            }
+        }
+    }
+
+    let start = (
+        grid.row(0)
+            .iter()
+            .enumerate()
+            .find(|(_, &c)| c == Some(true))
+            .unwrap()
+            .0,
+        0,
+    );
+    let start_facing = (1, 0);
+
+    let (pos, facing) = path
+        .iter()
+        .fold((start, start_facing), |(pos, facing), p| match p {
+            Path::Turn(d) => (pos, turn(facing, d)),
+            Path::Move(n) => GraphIterator::new(&nodes, &pos, &facing)
+                .take(*n)
+                .last()
+                .unwrap_or((pos, facing)),
+        });
+
+    score(&pos, &facing)
+}
+
+/// The faces of the puzzle input's cube net, by row and column of 50 tiles,
+/// which the stitches of part 2 are made for.
+const NET: [&str; 4] = [".##", ".#.", "##.", "#.."];
+
+/// The puzzle's cube net with scattered walls, and a path of `size` moves.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    const FACE: usize = 50;
+
+    let mut result = String::new();
+    for (y, faces) in NET.iter().flat_map(|row| [row; FACE]).enumerate() {
+        let row = faces
+            .chars()
+            .flat_map(|face| [face; FACE])
+            .enumerate()
+            .map(|(x, face)| match face {
+                '.' => ' ',
+                _ if (x, y) == (FACE, 0) || !rng.chance(1, 10) => '.',
+                _ => '#',
+            })
+            .collect::<String>();
+        result += &format!("{}\n", row.trim_end());
+    }
+
+    result += "\n";
+    for i in 0..size.max(1) {
+        if i > 0 {
+            result.push(*rng.pick(&['L', 'R']));
+        }
+        result += &rng.range(1..=50).to_string();
+    }
+    result + "\n"
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 22;
+    const SECTIONS: &'static [Section] = &[
+        Section {
+            line: "a row of the map",
+            allowed: "`.`, `#` or a space",
+            chars: |c| ".# ".contains(c),
+            ..Section::TEXT
+        },
+        Section {
+            line: "the path",
+            allowed: "a digit, `L` or `R`",
+            chars: |c| c.is_ascii_digit() || c == 'L' || c == 'R',
+            count: 1..=1,
+            ..Section::TEXT
+        },
+    ];
+
+    type Parsed = Notes;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(notes: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(notes).into())
+    }
+
+    fn part2(notes: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(notes, &STITCHES).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 6032);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(
+            &parse_input(INPUT).unwrap(),
+            &[
+                ((8, 0), (11, 0), (0, -1), (3, 4), (0, 4), (0, -1)),
+                ((8, 0), (8, 3), (-1, 0), (4, 4), (7, 4), (0, -1)),
+                ((11, 0), (11, 3), (1, 0), (11, 11), (11, 8), (1, 0)),
+                ((11, 4), (11, 7), (1, 0), (15, 8), (12, 8), (0, -1)),
+                ((0, 4), (0, 7), (-1, 0), (15, 11), (12, 11), (0, 1)),
+                ((0, 7), (3, 7), (0, 1), (11, 11), (8, 11), (0, 1)),
+                ((4, 7), (7, 7), (0, 1), (8, 11), (8, 8), (-1, 0)),
+            ],
+        );
+        assert_eq!(result, 5031);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replacen("10R5L5R10L4R5L5", "10R5L5X10L4R5L5", 1);
+        let error = parse_input(&input).err().unwrap();
+        assert_eq!((error.line, error.column), (14, 7));
+        assert_eq!(error.expected, "a distance, `L` or `R`");
+    }
+}
@@ -0,0 +1,244 @@
+use std::cmp::{max, min};
+
+use crate::{
+    error::{lines, ParseError, SolveError},
+    generate::Rng,
+    grid::Grid,
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+    trace,
+};
+
+type Point = (isize, isize);
+
+/// The elves' positions, at least one.
+fn parse_input(input: &str) -> Result<Vec<Point>, ParseError> {
+    for line in lines(input) {
+        if let Some((i, c)) = line
+            .text
+            .char_indices()
+            .find(|(_, c)| !matches!(c, '#' | '.'))
+        {
+            return Err(line.error(&line.text[i..i + c.len_utf8()], "`#` or `.`"));
+        }
+    }
+
+    let elves = Grid::parse(input, |c| c == '#')
+        .iter()
+        .filter(|(_, elf)| **elf)
+        .map(|((x, y), _)| (x as isize, y as isize))
+        .collect::<Vec<_>>();
+    if elves.is_empty() {
+        return Err(ParseError::end_of_input(
+            lines(input).count(),
+            "an elf, marked `#`",
+        ));
+    }
+    Ok(elves)
+}
+
+/// Lays the elves out on a grid spanning just their bounding box.
+fn map(elves: &[Point]) -> Grid<bool> {
+    let (left, top) = elves
+        .iter()
+        .cloned()
+        .reduce(|(minx, miny), (x, y)| (min(minx, x), min(miny, y)))
+        .unwrap();
+    let (right, bottom) = elves
+        .iter()
+        .cloned()
+        .reduce(|(maxx, maxy), (x, y)| (max(maxx, x), max(maxy, y)))
+        .unwrap();
+
+    let mut grid = Grid::new(right.abs_diff(left) + 1, bottom.abs_diff(top) + 1, false)
+        .with_origin((left, top));
+    for elf in elves {
+        *grid.at_mut(*elf).unwrap() = true;
+    }
+    grid
+}
+
+fn compl(p: Point) -> Point {
+    match p {
+        (_, 0) => (0, 1),
+        (0, _) => (1, 0),
+        _ => panic!(),
+    }
+}
+
+fn mul((x, y): &Point, coef: isize) -> Point {
+    (x * coef, y * coef)
+}
+
+fn add((ax, ay): &Point, (bx, by): &Point) -> Point {
+    (ax + bx, ay + by)
+}
+
+fn is_neighbor((ax, ay): &Point, (bx, by): &Point) -> bool {
+    (ax, ay) != (bx, by) && ax.abs_diff(*bx) <= 1 && ay.abs_diff(*by) <= 1
+}
+
+fn turn<T: Iterator<Item = Point>>(elves: &[Point], vectors: &mut T) -> Vec<Point> {
+    let proposal_templates = vectors
+        .take(4)
+        .map(|v| {
+            (
+                v,
+                (-1..=1)
+                    .map(move |coef| add(&v, &mul(&compl(v), coef)))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    vectors.next();
+
+    let proposals = elves
+        .iter()
+        .map(|e| {
+            if !elves.iter().any(|o| is_neighbor(o, e)) {
+                *e
+            } else {
+                proposal_templates
+                    .iter()
+                    .find(|(_, pv)| pv.iter().all(|p| !elves.contains(&add(e, p))))
+                    .map_or(*e, |(p, _)| add(e, p))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let result = elves
+        .iter()
+        .zip(proposals.iter())
+        .map(|(e, p)| {
+            if proposals.iter().filter(|pp| pp == &p).count() > 1 {
+                e
+            } else {
+                p
+            }
+        })
+        .copied()
+        .collect::<Vec<_>>();
+
+    result
+}
+
+fn trace_round(n: usize, elves: &[Point], next_elves: &[Point]) {
+    trace!(
+        Steps,
+        "round {}: {} elves moved",
+        n,
+        elves.iter().zip(next_elves).filter(|(a, b)| a != b).count()
+    );
+    trace!(States, "{}\n", map(next_elves).render(|&elf| if elf { '#' } else { '.' }));
+}
+
+fn process_part1(init_elves: &[Point]) -> usize {
+    let mut vectors = [(0, -1), (0, 1), (-1, 0), (1, 0)].iter().copied().cycle();
+
+    let elves = (1..=10).fold(init_elves.to_vec(), |elves, n| {
+        let next_elves = turn(&elves, &mut vectors);
+        trace_round(n, &elves, &next_elves);
+        next_elves
+    });
+
+    map(&elves).cells().filter(|elf| !**elf).count()
+}
+
+fn process_part2(elves: &[Point]) -> usize {
+    let mut elves = elves.to_vec();
+
+    let mut vectors = [(0, -1), (0, 1), (-1, 0), (1, 0)].iter().copied().cycle();
+
+    (1..)
+        .take_while(|&n| {
+            let next_elves = turn(&elves, &mut vectors);
+            trace_round(n, &elves, &next_elves);
+            let done = elves == next_elves;
+            elves = next_elves;
+            !done
+        })
+        .last()
+        .map_or(1, |n| n + 1)
+}
+
+/// A square grove `size` tiles wide, about half of them elves. An elf in the
+/// top row always has one below it, so it steps north in the first round and
+/// the grove never starts out settled.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(2);
+    let mut grove = Grid::new(side, side, '.').map(|_| *rng.pick(&['.', '#']));
+    let x = rng.below(side);
+    grove[(x, 0)] = '#';
+    grove[(x, 1)] = '#';
+
+    grove
+        .rows()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 23;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a row of the scan",
+        allowed: "`.` or `#`",
+        chars: |c| c == '.' || c == '#',
+        grid: true,
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<Point>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(elves: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(process_part1(elves).into())
+    }
+
+    fn part2(elves: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(Some(process_part2(elves).into()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 110);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(result, 20);
+    }
+
+    #[test]
+    fn test_settled() {
+        let elves = parse_input("#\n").unwrap();
+        assert_eq!(process_part1(&elves), 0);
+        assert_eq!(process_part2(&elves), 1);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(parse_input("...\n...\n").unwrap_err().line, 3);
+        let error = parse_input("..#\n.E.\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+}
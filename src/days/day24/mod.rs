@@ -0,0 +1,354 @@
+use std::collections::HashSet;
+
+use crate::{
+    budget,
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    grid::Grid,
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+    trace,
+};
+
+type Point = (usize, usize);
+type WrapPoint = (isize, isize);
+type Dir = fn(&Point) -> WrapPoint;
+type Blizzard = (Point, Dir);
+
+/// The blizzards and the size of the area inside the walls.
+type Valley = (Vec<Blizzard>, Point);
+
+fn up((x, y): &Point) -> WrapPoint {
+    (*x as isize, *y as isize - 1)
+}
+fn down((x, y): &Point) -> WrapPoint {
+    (*x as isize, *y as isize + 1)
+}
+fn left((x, y): &Point) -> WrapPoint {
+    (*x as isize - 1, *y as isize)
+}
+fn right((x, y): &Point) -> WrapPoint {
+    (*x as isize + 1, *y as isize)
+}
+fn id((x, y): &Point) -> WrapPoint {
+    (*x as isize, *y as isize)
+}
+
+fn parse_blizzard(c: char) -> Option<Dir> {
+    match c {
+        '<' => Some(left),
+        '>' => Some(right),
+        '^' => Some(up),
+        'v' => Some(down),
+        _ => None,
+    }
+}
+
+/// Checks that `line` is a wall as wide as the valley with a gap at `gap`.
+fn parse_wall(line: &Line, width: usize, gap: usize) -> Result<(), ParseError> {
+    let wall = (0..width)
+        .map(|x| if x == gap { '.' } else { '#' })
+        .collect::<String>();
+    if line.text == wall {
+        Ok(())
+    } else {
+        Err(line.mismatch(&format!("a wall like `{}`", wall)))
+    }
+}
+
+fn parse_row(line: &Line, width: usize) -> Result<(), ParseError> {
+    let inner = line
+        .text
+        .strip_prefix('#')
+        .and_then(|text| text.strip_suffix('#'));
+    let Some(inner) = inner.filter(|_| line.text.len() == width) else {
+        return Err(line.mismatch(&format!("a row {} tiles wide between walls", width)));
+    };
+    match inner.char_indices().find(|&(_, c)| !".<>^v".contains(c)) {
+        Some((i, c)) => Err(line.error(&inner[i..i + c.len_utf8()], "`.`, `<`, `>`, `^` or `v`")),
+        None => Ok(()),
+    }
+}
+
+/// The blizzards, positioned inside the walls, and the size of that area.
+/// The valley has to be walled in, with the entrance at the left of the top
+/// row and the exit at the right of the bottom one.
+fn parse_input(input: &str) -> Result<Valley, ParseError> {
+    let rows = lines(input).collect::<Vec<_>>();
+    let [first, middle @ .., last] = &rows[..] else {
+        return Err(ParseError::end_of_input(
+            rows.len(),
+            "a valley between two walls",
+        ));
+    };
+    let width = first.text.len().max(3);
+    parse_wall(first, width, 1)?;
+    if middle.is_empty() {
+        return Err(last.mismatch("a row of the valley"));
+    }
+    for row in middle {
+        parse_row(row, width)?;
+    }
+    parse_wall(last, width, width - 2)?;
+
+    let map = Grid::parse(input, |c| c);
+
+    let blizzards = map
+        .iter()
+        .filter_map(|((x, y), c)| parse_blizzard(*c).map(|b| ((x - 1, y - 1), b)))
+        .collect();
+
+    Ok((blizzards, (map.width() - 2, map.height() - 2)))
+}
+
+fn next_blizzards(blizzards: &[Blizzard], (width, height): Point) -> Vec<Blizzard> {
+    blizzards
+        .iter()
+        .map(|(p, f)| {
+            let (nx, ny) = f(p);
+            (
+                (
+                    nx.rem_euclid(width as isize) as usize,
+                    ny.rem_euclid(height as isize) as usize,
+                ),
+                *f,
+            )
+        })
+        .collect()
+}
+
+/// Draws the valley inside its walls, with blizzards as `*` and the
+/// positions the expedition may be in as `E`.
+fn format(blizzards: &[Blizzard], positions: &[Point], (width, height): Point) -> String {
+    let mut valley = Grid::new(width, height, '.');
+    for (blizzard, _) in blizzards {
+        valley[*blizzard] = '*';
+    }
+    for position in positions {
+        if let Some(tile) = valley.get_mut(*position) {
+            *tile = 'E';
+        }
+    }
+    valley.render(|&tile| tile)
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Minutes to get from `start` to `end`, or `None` if there is no way
+/// through or the budget is spent first.
+fn process(
+    blizzards: &mut Vec<Blizzard>,
+    start: Point,
+    end: Point,
+    (width, height): Point,
+) -> Option<usize> {
+    let mut positions = vec![start];
+    // The blizzards are back where they started after this many minutes, so
+    // being somewhere again at the same point of the cycle leads nowhere new.
+    let period = width / gcd(width, height) * height;
+    let mut seen = HashSet::from([(0, start)]);
+
+    let dirs = [up, down, left, right, id];
+    trace!(Steps, "from {:?} to {:?}", start, end);
+
+    for i in 1.. {
+        if budget::spent() {
+            return None;
+        }
+
+        let mut nb = next_blizzards(blizzards, (width, height));
+        blizzards.clear();
+        blizzards.append(&mut nb);
+        positions = positions
+            .iter()
+            .flat_map(|p| {
+                dirs.iter()
+                    .map(|f| f(p))
+                    .filter_map(|(px, py)| px.try_into().ok().zip(py.try_into().ok()))
+                    .filter(|&(nx, ny): &Point| {
+                        (nx < width
+                            && ny < height
+                            && !blizzards.iter().any(|(b, _)| b == &(nx, ny)))
+                            || (nx, ny) == end
+                            || (nx, ny) == start
+                    })
+            })
+            .collect();
+
+        trace!(Steps, "minute {}: {} positions", i, positions.len());
+        trace!(States, "{}\n", format(blizzards, &positions, (width, height)));
+
+        if positions.contains(&end) {
+            return Some(i);
+        }
+
+        positions.retain(|&p| seen.insert((i % period, p)));
+        if positions.is_empty() {
+            return None;
+        }
+    }
+
+    None
+}
+
+fn process_part1((blizzards, (width, height)): &Valley) -> Option<usize> {
+    let (mut blizzards, (width, height)) = (blizzards.clone(), (*width, *height));
+
+    let start = (0, 0);
+    let end = (width - 1, height);
+
+    process(&mut blizzards, start, end, (width, height))
+}
+
+fn process_part2((blizzards, (width, height)): &Valley) -> Option<usize> {
+    let (mut blizzards, (width, height)) = (blizzards.clone(), (*width, *height));
+
+    let start = (0, 0);
+    let end = (width - 1, height);
+
+    Some(
+        process(&mut blizzards, start, end, (width, height))?
+            + process(&mut blizzards, end, start, (width, height))?
+            + process(&mut blizzards, start, end, (width, height))?,
+    )
+}
+
+/// A valley `size` tiles wide, at least three, and a fifth as high, with a
+/// blizzard on about a quarter of its tiles. As in the puzzle, none blow up
+/// or down the columns of the entrance and the exit, and there is a way
+/// there and back; valleys without one are drawn again.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    loop {
+        let input = generate_valley(rng, size);
+        if process_part2(&parse_input(&input).unwrap()).is_some() {
+            return input;
+        }
+    }
+}
+
+fn generate_valley(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(3);
+    let height = (width / 5).max(2);
+
+    let mut result = format!("#.{}\n", "#".repeat(width));
+    for _ in 0..height {
+        result += "#";
+        for x in 0..width {
+            let blizzards: &[char] = if x == 0 || x == width - 1 {
+                &['<', '>']
+            } else {
+                &['<', '>', '^', 'v']
+            };
+            result.push(if rng.chance(1, 4) {
+                *rng.pick(blizzards)
+            } else {
+                '.'
+            });
+        }
+        result += "#\n";
+    }
+    result + &format!("{}.#\n", "#".repeat(width))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 24;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a row of the valley",
+        allowed: "`#`, `.`, `<`, `>`, `^` or `v`",
+        chars: |c| "#.<>^v".contains(c),
+        grid: true,
+        ..Section::TEXT
+    }];
+
+    type Parsed = Valley;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(valley: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        match process_part1(valley) {
+            Some(minutes) => Ok(minutes.into()),
+            None => Err(SolveError::no_answer("way through the valley")),
+        }
+    }
+
+    fn part2(valley: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        match process_part2(valley) {
+            Some(minutes) => Ok(Some(minutes.into())),
+            None => Err(SolveError::no_answer("way through the valley and back")),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::Budget;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, Some(18));
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = process_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(result, Some(54));
+    }
+
+    #[test]
+    fn test_no_way_through() {
+        let valley = parse_input("#.########\n#.....>.<#\n#..^v...<#\n########.#\n").unwrap();
+        assert_eq!(process_part1(&valley), None);
+        let error = Solver::part1(&valley, &Params::default()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 0, part 0: found no way through the valley"
+        );
+
+        // Seed 1 used to draw that valley, on which the search never ended.
+        let input = generate_input(&mut Rng::new(1), 8);
+        assert!(process_part2(&parse_input(&input).unwrap()).is_some());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = |input| parse_input(input).unwrap_err();
+        assert_eq!(error("#.#\n###\n").expected, "a row of the valley");
+        assert_eq!(error("#.#\n").line, 2);
+        let wall = error("#.###\n#...#\n#.###\n");
+        assert_eq!(
+            (wall.line, wall.expected.as_str()),
+            (3, "a wall like `###.#`")
+        );
+        let tile = error("#.####\n#.é.#\n####.#\n");
+        assert_eq!((tile.line, tile.column), (2, 3));
+        assert_eq!(error("#.###\n#..#\n###.#\n").line, 2);
+    }
+
+    #[test]
+    fn test_timed_out() {
+        let valley = parse_input(INPUT).unwrap();
+        let budget = Budget::unlimited();
+        budget.cancel();
+        let (result, stopped) = budget::limit(&budget, || process_part1(&valley));
+        assert_eq!((result, stopped), (None, true));
+    }
+}
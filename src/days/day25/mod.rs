@@ -0,0 +1,142 @@
+use crate::{
+    error::{lines, Line, ParseError, SolveError},
+    generate::Rng,
+    lint::Section,
+    params::Params,
+    solution::{Answer, Solution},
+};
+
+fn snafu_digit(snafu: char) -> Option<i128> {
+    match snafu {
+        '-' => Some(-1),
+        '=' => Some(-2),
+        _ => snafu.to_digit(3).map(i128::from),
+    }
+}
+
+fn snafu_to_int(line: Line) -> Result<i128, ParseError> {
+    let snafu = line.text;
+    snafu.char_indices().try_fold(0, |agg: i128, (at, d)| {
+        let digit = snafu_digit(d)
+            .ok_or_else(|| line.error(&snafu[at..at + d.len_utf8()], "a SNAFU digit"))?;
+        agg.checked_mul(5)
+            .and_then(|agg| agg.checked_add(digit))
+            .ok_or_else(|| line.mismatch("a SNAFU number that fits in 128 bits"))
+    })
+}
+
+/// Balanced base 5: each remainder of 3 or 4 is written as -2 or -1 and
+/// carries one into the next digit. Negative numbers work the same way.
+fn int_to_snafu(int: i128) -> String {
+    if int == 0 {
+        return "0".to_owned();
+    }
+
+    let mut digits = vec![];
+    let mut rest = int;
+    while rest != 0 {
+        let (digit, carry) = match rest.rem_euclid(5) {
+            0 => ('0', 0),
+            1 => ('1', 0),
+            2 => ('2', 0),
+            3 => ('=', 1),
+            _ => ('-', 1),
+        };
+        digits.push(digit);
+        rest = rest.div_euclid(5) + carry;
+    }
+    digits.into_iter().rev().collect()
+}
+
+fn parse_input(input: &str) -> Result<Vec<i128>, ParseError> {
+    lines(input).map(snafu_to_int).collect()
+}
+
+/// The sum in SNAFU, or `None` if it doesn't fit in 128 bits.
+fn process_part1(numbers: &[i128]) -> Option<String> {
+    let sum = numbers
+        .iter()
+        .try_fold(0, |sum: i128, &n| sum.checked_add(n))?;
+    Some(int_to_snafu(sum))
+}
+
+/// `size` SNAFU numbers of up to thirteen digits.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let digits = rng.range(1..=13) as u32;
+            int_to_snafu(rng.range(1..=5_i64.pow(digits) / 2).into()) + "\n"
+        })
+        .collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 25;
+    const SECTIONS: &'static [Section] = &[Section {
+        line: "a SNAFU number",
+        allowed: "`2`, `1`, `0`, `-` or `=`",
+        chars: |c| "210-=".contains(c),
+        ..Section::TEXT
+    }];
+
+    type Parsed = Vec<i128>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(numbers: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        match process_part1(numbers) {
+            Some(sum) => Ok(sum.into()),
+            None => Err(SolveError::no_answer("sum that fits in 128 bits")),
+        }
+    }
+
+    fn part2(_numbers: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+        Ok(None)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = process_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(result, Some("2=-1=0".to_string()));
+    }
+
+    #[test]
+    fn test_snafu() {
+        for n in -200..=200 {
+            let snafu = int_to_snafu(n);
+            let line = Line {
+                number: 1,
+                text: &snafu,
+            };
+            assert_eq!(snafu_to_int(line), Ok(n), "{}", snafu);
+        }
+        let sum = |input: &str| process_part1(&parse_input(input).unwrap());
+        assert_eq!(sum("-"), Some("-".to_string()));
+        assert_eq!(sum("=\n-"), Some("-2".to_string()));
+
+        // 30 digits, past what an `isize` holds.
+        let long = "1".to_string() + &"0".repeat(29);
+        assert_eq!(
+            sum(&format!("{}\n{}", long, long)),
+            Some("2".to_string() + &"0".repeat(29))
+        );
+        assert_eq!(parse_input(&"2".repeat(56)).err().map(|e| e.line), Some(1));
+        let half = "2".to_string() + &"0".repeat(54);
+        assert_eq!(sum(&format!("{}\n{}", half, half)), None);
+    }
+}
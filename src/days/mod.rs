@@ -0,0 +1,22 @@
+use crate::solution::Day;
+
+/// Declares the day modules and registers each one's `Solver`, so adding a
+/// day only means adding it to the list below.
+macro_rules! days {
+    ($($module:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        pub fn all() -> Vec<Day> {
+            vec![$(Day::of::<$module::Solver>()),*]
+        }
+    };
+}
+
+days!(
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+);
+
+pub fn get(number: u8) -> Option<Day> {
+    all().into_iter().find(|day| day.number == number)
+}
@@ -0,0 +1,216 @@
+use std::{
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::{panics::quietly, solution::Day};
+
+/// A generated input on which a day's solution and its reference disagree.
+pub struct Disagreement {
+    pub day: u8,
+    pub part: u8,
+    pub seed: u64,
+    /// The smallest input found that still shows the disagreement.
+    pub input: String,
+    pub reference: String,
+    /// The solution's answer, error or panic message.
+    pub solution: String,
+}
+
+enum Comparison {
+    /// The reference has no answer for the input, so there is nothing to check.
+    Unchecked,
+    Agreed,
+    Disagreed {
+        reference: String,
+        solution: String,
+    },
+}
+
+/// Runs `f`, turning its answer into text and both its errors and its
+/// panics into messages.
+fn outcome<A: ToString, E: ToString>(
+    f: impl FnOnce() -> Result<Option<A>, E>,
+) -> Result<Option<String>, String> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result
+            .map(|answer| answer.map(|a| a.to_string()))
+            .map_err(|e| e.to_string()),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(format!("panicked: {}", message))
+        }
+    }
+}
+
+/// Inputs the reference cannot answer are taken as invalid, and are never
+/// held against the solution.
+fn compare(day: &Day, part: u8, input: &str) -> Comparison {
+    let reference = match outcome(|| day.reference(part, input)) {
+        Ok(Some(answer)) => answer,
+        _ => return Comparison::Unchecked,
+    };
+    match outcome(|| day.solve(part, input)) {
+        Ok(Some(answer)) if answer == reference => Comparison::Agreed,
+        Ok(Some(solution)) | Err(solution) => Comparison::Disagreed {
+            reference,
+            solution,
+        },
+        Ok(None) => Comparison::Disagreed {
+            reference,
+            solution: "no answer".to_string(),
+        },
+    }
+}
+
+/// Removes lines from `input` for as long as `keep` still holds, first in
+/// large chunks and then one at a time.
+fn minimise(input: &str, keep: impl Fn(&str) -> bool) -> String {
+    let join =
+        |lines: &[&str]| -> String { lines.iter().map(|line| format!("{}\n", line)).collect() };
+    let mut lines = input.lines().collect::<Vec<_>>();
+    let mut chunk = lines.len().div_ceil(2).max(1);
+
+    loop {
+        let mut removed = false;
+        let mut start = 0;
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate = [&lines[..start], &lines[end..]].concat();
+            if !candidate.is_empty() && keep(&join(&candidate)) {
+                lines = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+
+        if chunk > 1 {
+            chunk /= 2;
+        } else if !removed {
+            return join(&lines);
+        }
+    }
+}
+
+/// Compares the day's solution of each part with its reference on inputs
+/// generated from `seeds`. Returns how many comparisons were made, or the
+/// first disagreement with its input minimised.
+pub fn search(
+    day: &Day,
+    parts: &[u8],
+    seeds: Range<u64>,
+    size: usize,
+) -> Result<usize, Disagreement> {
+    quietly(|| {
+        let mut checked = 0;
+        for seed in seeds {
+            let input = day.generate(seed, size);
+            for &part in parts {
+                match compare(day, part, &input) {
+                    Comparison::Unchecked => continue,
+                    Comparison::Agreed => checked += 1,
+                    Comparison::Disagreed { .. } => {
+                        let input = minimise(&input, |candidate| {
+                            matches!(compare(day, part, candidate), Comparison::Disagreed { .. })
+                        });
+                        let Comparison::Disagreed {
+                            reference,
+                            solution,
+                        } = compare(day, part, &input)
+                        else {
+                            unreachable!()
+                        };
+                        return Err(Disagreement {
+                            day: day.number,
+                            part,
+                            seed,
+                            input,
+                            reference,
+                            solution,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(checked)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        days,
+        error::{ParseError, SolveError},
+        generate::Rng,
+        params::Params,
+        solution::{Answer, Solution},
+    };
+
+    /// Sums the numbers on its lines, except that it gets 7 wrong.
+    struct Faulty;
+
+    impl Solution for Faulty {
+        const DAY: u8 = 0;
+
+        type Parsed = Vec<u64>;
+
+        fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+            Ok(input.lines().map(|n| n.parse().unwrap()).collect())
+        }
+
+        fn part1(numbers: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+            if numbers.contains(&7) {
+                panic!("unlucky");
+            }
+            Ok(numbers.iter().sum::<u64>().into())
+        }
+
+        fn part2(_numbers: &Self::Parsed, _params: &Params) -> Result<Option<Answer>, SolveError> {
+            Ok(None)
+        }
+
+        fn generate(rng: &mut Rng, size: usize) -> String {
+            (0..size)
+                .map(|_| format!("{}\n", rng.range(1..=9)))
+                .collect()
+        }
+
+        fn reference(part: u8, input: &str) -> Result<Option<String>, ParseError> {
+            Ok((part == 1).then(|| {
+                input
+                    .lines()
+                    .map(|n| n.parse::<u64>().unwrap())
+                    .sum::<u64>()
+                    .to_string()
+            }))
+        }
+    }
+
+    #[test]
+    fn test_minimise() {
+        let disagreement = search(&Day::of::<Faulty>(), &[1, 2], 0..10, 30)
+            .err()
+            .unwrap();
+        assert_eq!(disagreement.input, "7\n");
+        assert_eq!(disagreement.reference, "7");
+        assert_eq!(disagreement.solution, "panicked: unlucky");
+    }
+
+    /// Day 19 is left out: both its solutions are too slow in a debug build,
+    /// and its pruning is known to disagree with the reference on some
+    /// blueprints, as the README describes.
+    #[test]
+    fn test_references_agree() {
+        for (number, size) in [(11, 4), (15, 3), (17, 3)] {
+            let day = days::get(number).unwrap();
+            let result = search(&day, &[1], 0..2, size);
+            assert!(matches!(result, Ok(1..)), "day {}", number);
+        }
+    }
+}
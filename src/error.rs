@@ -0,0 +1,251 @@
+use std::{fmt, io, str::FromStr};
+
+use crate::solution::Answer;
+
+/// A malformed puzzle input: where it went wrong and what was expected there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Day whose input failed to parse; filled in by [`crate::solution::Day`],
+    /// so parsers don't need to know which day they belong to.
+    pub day: u8,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: &str, found: &str) -> Self {
+        Self {
+            day: 0,
+            line,
+            column,
+            expected: expected.to_string(),
+            found: found.to_string(),
+        }
+    }
+
+    /// The input stopped before something required, after `lines` lines.
+    pub fn end_of_input(lines: usize, expected: &str) -> Self {
+        Self::new(lines + 1, 1, expected, "end of input")
+    }
+
+    pub fn in_day(self, day: u8) -> Self {
+        Self { day, ..self }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found {}",
+            self.day, self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Why a part has no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    Parse(ParseError),
+    /// Every problem a part's reading of the input found, in input order,
+    /// where the parser let the input through for a more lenient reading,
+    /// like day 1's strict one.
+    Problems(Vec<ParseError>),
+    /// The input parsed but has no answer: the part found no `missing`, like
+    /// day 15's "position for the distress beacon".
+    NoAnswer {
+        day: u8,
+        part: u8,
+        missing: String,
+    },
+    /// The part's [`Budget`](crate::budget::Budget) ran out before it
+    /// finished. `best` is the best answer it had by then, for searches that
+    /// improve on one as they go.
+    TimedOut {
+        day: u8,
+        part: u8,
+        best: Option<Answer>,
+    },
+}
+
+impl SolveError {
+    pub fn no_answer(missing: &str) -> Self {
+        Self::NoAnswer {
+            day: 0,
+            part: 0,
+            missing: missing.to_string(),
+        }
+    }
+
+    /// Fills in the day and part, which solutions leave to
+    /// [`crate::solution::Day`] as they do for [`ParseError`].
+    pub fn in_part(self, day: u8, part: u8) -> Self {
+        match self {
+            Self::Parse(e) => Self::Parse(e.in_day(day)),
+            Self::Problems(problems) => {
+                Self::Problems(problems.into_iter().map(|e| e.in_day(day)).collect())
+            }
+            Self::NoAnswer { missing, .. } => Self::NoAnswer { day, part, missing },
+            Self::TimedOut { best, .. } => Self::TimedOut { day, part, best },
+        }
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(e) => e.fmt(f),
+            Self::Problems(problems) => {
+                let lines = problems.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(f, "{}", lines.join("\n"))
+            }
+            Self::NoAnswer { day, part, missing } => {
+                write!(f, "day {}, part {}: found no {}", day, part, missing)
+            }
+            Self::TimedOut { day, part, best } => {
+                write!(f, "day {}, part {}: timed out", day, part)?;
+                match best {
+                    Some(best) => write!(f, ", best answer so far {}", best),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Why a day couldn't parse an input as it read it, with
+/// [`crate::solution::Solution::parse_stream`] or
+/// [`crate::solution::Solution::stats`].
+#[derive(Debug)]
+pub enum StreamError {
+    Read(io::Error),
+    Parse(ParseError),
+    /// Every problem found, in input order, by a reading that goes on past
+    /// the first, like day 1's strict one.
+    Problems(Vec<ParseError>),
+}
+
+impl StreamError {
+    /// Fills in the day, as [`SolveError::in_part`] does.
+    pub fn in_day(self, day: u8) -> Self {
+        match self {
+            Self::Read(e) => Self::Read(e),
+            Self::Parse(e) => Self::Parse(e.in_day(day)),
+            Self::Problems(problems) => {
+                Self::Problems(problems.into_iter().map(|e| e.in_day(day)).collect())
+            }
+        }
+    }
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(e) => write!(f, "cannot read input: {}", e),
+            Self::Parse(e) => e.fmt(f),
+            Self::Problems(problems) => SolveError::Problems(problems.clone()).fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> Self {
+        Self::Read(e)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+/// A line of puzzle input, which knows its position for error reporting.
+#[derive(Clone, Copy)]
+pub struct Line<'a> {
+    /// 1-based line number.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Column of `at`, which should be a slice of this line's text; falls back
+    /// to the start of the line for unrelated strings.
+    pub fn column(&self, at: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize).wrapping_sub(start);
+        if offset <= self.text.len() && self.text.is_char_boundary(offset) {
+            self.text[..offset].chars().count() + 1
+        } else {
+            1
+        }
+    }
+
+    /// An error pointing at `at`, a slice of this line.
+    pub fn error(&self, at: &str, expected: &str) -> ParseError {
+        let found = if at.is_empty() {
+            "end of line".to_string()
+        } else {
+            format!("`{}`", at)
+        };
+        ParseError::new(self.number, self.column(at), expected, &found)
+    }
+
+    /// An error about the line as a whole not having the expected shape.
+    pub fn mismatch(&self, expected: &str) -> ParseError {
+        self.error(self.text, expected)
+    }
+
+    /// Parses `at`, a slice of this line, reporting `expected` on failure.
+    pub fn parse<T: FromStr>(&self, at: &str, expected: &str) -> Result<T, ParseError> {
+        at.parse().map_err(|_| self.error(at, expected))
+    }
+}
+
+/// The input's lines, numbered from 1.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column() {
+        let line = lines("a\n12-x4").nth(1).unwrap();
+        let error = line.parse::<u8>(&line.text[3..], "a number").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(
+            error.in_day(4).to_string(),
+            "day 4, line 2, column 4: expected a number, found `x4`"
+        );
+    }
+
+    #[test]
+    fn test_end_of_line() {
+        let line = lines("move 1 from").next().unwrap();
+        let error = line.error(&line.text[11..], "a stack number");
+        assert_eq!(error.column, 12);
+        assert_eq!(error.found, "end of line");
+    }
+}
@@ -0,0 +1,109 @@
+use std::ops::RangeInclusive;
+
+/// A small seeded random number generator (SplitMix64), so generated inputs
+/// are the same for the same seed on every machine and every run.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, for a non-zero `n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let span = range.end().abs_diff(*range.start()) + 1;
+        range.start().wrapping_add((self.next_u64() % span) as i64)
+    }
+
+    /// True with a probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// The letters `a` to `z`, which many puzzle formats are made of.
+pub const LOWERCASE: [char; 26] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
+    't', 'u', 'v', 'w', 'x', 'y', 'z',
+];
+
+/// A name of `len` random lowercase letters.
+pub fn name(rng: &mut Rng, len: usize) -> String {
+    (0..len).map(|_| *rng.pick(&LOWERCASE)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    /// Parts too slow to solve in a debug build: day 15 scans up to four
+    /// million rows, and day 19 searches a long time for every blueprint.
+    const SLOW: [(u8, u8); 3] = [(15, 2), (19, 1), (19, 2)];
+
+    #[test]
+    fn test_rng() {
+        let sample = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.range(-3..=3)).collect::<Vec<_>>()
+        };
+        assert!(sample(7).iter().all(|n| (-3..=3).contains(n)));
+        assert!((-3..=3).all(|n| sample(7).contains(&n)));
+        assert_eq!(sample(7), sample(7));
+        assert_ne!(sample(7), sample(8));
+    }
+
+    #[test]
+    fn test_deterministic() {
+        for day in days::all() {
+            assert_eq!(day.generate(1, 5), day.generate(1, 5), "day {}", day.number);
+            assert_ne!(day.generate(1, 5), day.generate(2, 5), "day {}", day.number);
+        }
+    }
+
+    #[test]
+    fn test_every_day() {
+        for day in days::all() {
+            for seed in 0..2 {
+                let input = day.generate(seed, 3);
+                day.parse(&input).unwrap();
+                for part in [1, 2] {
+                    if !SLOW.contains(&(day.number, part)) {
+                        let answer = day.solve(part, &input);
+                        assert!(
+                            answer.is_ok(),
+                            "day {}, seed {}: {:?}",
+                            day.number,
+                            seed,
+                            answer
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
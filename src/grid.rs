@@ -0,0 +1,305 @@
+use std::ops::{Index, IndexMut, Range};
+
+/// Cell coordinates inside a grid: `(x, y)`, i.e. column then row.
+pub type Point = (usize, usize);
+
+/// Coordinates relative to a grid's origin, which may lie outside the grid.
+pub type Coord = (isize, isize);
+
+/// Unit steps towards the four orthogonal neighbours.
+pub const ORTHOGONAL: [Coord; 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Unit steps towards all eight neighbours, row by row.
+pub const ALL_DIRECTIONS: [Coord; 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular, row-major grid of cells.
+///
+/// Cells are addressed by [`Point`]s starting at the top left corner. The
+/// grid may also be placed at an `origin`, so cells can be addressed with the
+/// puzzle's own [`Coord`]s (like day 14's sand, which starts at column 500).
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    origin: Coord,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            origin: (0, 0),
+        }
+    }
+
+    /// Builds a grid from rows, padding short rows with `fill`.
+    pub fn from_rows(rows: Vec<Vec<T>>, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for mut row in rows {
+            row.resize(width, fill.clone());
+            cells.append(&mut row);
+        }
+
+        Self {
+            cells,
+            width,
+            origin: (0, 0),
+        }
+    }
+
+    /// Parses a character map, one row per line. Short lines are padded as if
+    /// they ended in spaces.
+    pub fn parse<F: FnMut(char) -> T>(input: &str, mut f: F) -> Self
+    where
+        T: Clone,
+    {
+        let rows = input
+            .lines()
+            .map(|line| line.chars().map(&mut f).collect())
+            .collect();
+        Self::from_rows(rows, f(' '))
+    }
+
+    /// Places the grid's top left cell at `origin`.
+    pub fn with_origin(mut self, origin: Coord) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or_default()
+    }
+
+    pub fn origin(&self) -> Coord {
+        self.origin
+    }
+
+    pub fn contains(&self, (x, y): Point) -> bool {
+        x < self.width && y < self.height()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if self.contains(point) {
+            Some(&self.cells[point.1 * self.width + point.0])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            Some(&mut self.cells[point.1 * self.width + point.0])
+        } else {
+            None
+        }
+    }
+
+    /// Converts origin-relative coordinates into a point, if inside the grid.
+    pub fn local(&self, (x, y): Coord) -> Option<Point> {
+        let point = (
+            usize::try_from(x - self.origin.0).ok()?,
+            usize::try_from(y - self.origin.1).ok()?,
+        );
+        Some(point).filter(|p| self.contains(*p))
+    }
+
+    /// Converts a point into origin-relative coordinates.
+    pub fn global(&self, (x, y): Point) -> Coord {
+        (x as isize + self.origin.0, y as isize + self.origin.1)
+    }
+
+    /// The cell at origin-relative coordinates.
+    pub fn at(&self, coord: Coord) -> Option<&T> {
+        self.local(coord).and_then(|p| self.get(p))
+    }
+
+    pub fn at_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.local(coord).and_then(|p| self.get_mut(p))
+    }
+
+    /// The point one `step` away from `point`, if still inside the grid.
+    pub fn step(&self, (x, y): Point, (dx, dy): Coord) -> Option<Point> {
+        let next = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        Some(next).filter(|p| self.contains(*p))
+    }
+
+    /// The orthogonal neighbours of `point` that are inside the grid.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        ORTHOGONAL.iter().filter_map(move |d| self.step(point, *d))
+    }
+
+    /// All neighbours of `point`, diagonals included, that are inside the grid.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        ALL_DIRECTIONS
+            .iter()
+            .filter_map(move |d| self.step(point, *d))
+    }
+
+    /// The points met walking from `point` (excluded) in `direction` until the
+    /// edge of the grid.
+    pub fn ray(&self, point: Point, direction: Coord) -> impl Iterator<Item = Point> + '_ {
+        let mut current = point;
+        std::iter::from_fn(move || {
+            current = self.step(current, direction)?;
+            Some(current)
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of a range of rows, as one contiguous slice.
+    pub fn row_span(&self, rows: Range<usize>) -> &[T] {
+        &self.cells[rows.start * self.width..rows.end * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn push_row(&mut self, mut row: Vec<T>) {
+        assert_eq!(row.len(), self.width, "row width must match the grid");
+        self.cells.append(&mut row);
+    }
+
+    pub fn pop_row(&mut self) -> Option<Vec<T>> {
+        let height = self.height();
+        if height == 0 {
+            None
+        } else {
+            Some(self.cells.split_off((height - 1) * self.width))
+        }
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// The first point, in reading order, whose cell satisfies `predicate`.
+    pub fn position<P: FnMut(&T) -> bool>(&self, mut predicate: P) -> Option<Point> {
+        self.iter().find(|(_, c)| predicate(c)).map(|(p, _)| p)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            origin: self.origin,
+        }
+    }
+
+    /// Draws the grid as text, one line per row.
+    pub fn render<F: FnMut(&T) -> char>(&self, mut f: F) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&mut f).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).expect("point outside the grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point).expect("point outside the grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static MAP: &str = "#.#\n..\n.##";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(MAP, |c| c);
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[(2, 1)], ' ');
+        assert_eq!(grid.render(|c| *c), "#.#\n.. \n.##");
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 2, 0);
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = Grid::parse(MAP, |c| c == '#');
+        assert_eq!(grid.row(2), &[false, true, true]);
+        assert_eq!(
+            grid.column(0).copied().collect::<Vec<_>>(),
+            vec![true, false, false]
+        );
+        assert_eq!(
+            grid.ray((2, 2), (-1, -1)).collect::<Vec<_>>(),
+            vec![(1, 1), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_origin() {
+        let grid = Grid::new(3, 2, '.').with_origin((498, -1));
+        assert_eq!(grid.local((500, 0)), Some((2, 1)));
+        assert_eq!(grid.local((501, 0)), None);
+        assert_eq!(grid.global((0, 0)), (498, -1));
+        assert_eq!(grid.at((497, 0)), None);
+    }
+
+    #[test]
+    fn test_rows() {
+        let mut grid = Grid::new(2, 1, 0);
+        grid.push_row(vec![1, 2]);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.row_span(1..2), &[1, 2]);
+        assert_eq!(grid.pop_row(), Some(vec![1, 2]));
+        assert_eq!(grid.height(), 1);
+    }
+}
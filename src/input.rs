@@ -0,0 +1,197 @@
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+/// Where a day's puzzle input is read from.
+pub enum Source {
+    /// The `input.txt` checked in next to the day's solution.
+    Bundled,
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// Interprets a command line argument: `-` is stdin, anything else a path.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub day: u8,
+    pub origin: String,
+    pub error: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}: cannot read input from {}: {}",
+            self.day, self.origin, self.error
+        )
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// A file checked in next to the day's solution.
+pub fn day_file(day: u8, name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src/days")
+        .join(format!("day{:02}", day))
+        .join(name)
+}
+
+pub fn bundled_path(day: u8) -> PathBuf {
+    day_file(day, "input.txt")
+}
+
+pub fn load(day: u8, source: &Source) -> Result<String, InputError> {
+    let (origin, result) = match source {
+        Source::Bundled => {
+            let path = bundled_path(day);
+            (format!("`{}`", path.display()), fs::read_to_string(path))
+        }
+        Source::File(path) => (format!("`{}`", path.display()), fs::read_to_string(path)),
+        Source::Stdin => {
+            let mut buf = String::new();
+            let result = io::stdin().read_to_string(&mut buf).map(|_| buf);
+            ("stdin".to_string(), result)
+        }
+    };
+
+    result.map_err(|error| InputError { day, origin, error })
+}
+
+/// A day's input read a piece at a time rather than [`load`]ed whole, for
+/// inputs too large to hold; it is [`hash`]ed as it is read.
+pub struct Stream {
+    day: u8,
+    origin: String,
+    reader: Box<dyn BufRead>,
+    hash: u64,
+}
+
+impl Stream {
+    /// The hash of what has been read so far.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// An error met partway through, as [`load`] would report it.
+    pub fn error(&self, error: io::Error) -> InputError {
+        InputError {
+            day: self.day,
+            origin: self.origin.clone(),
+            error,
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.reader.read(buf)?;
+        self.hash = fnv(self.hash, &buf[..n]);
+        Ok(n)
+    }
+}
+
+impl BufRead for Stream {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        // What is consumed was filled already, so this reads nothing.
+        if let Ok(buf) = self.reader.fill_buf() {
+            self.hash = fnv(self.hash, &buf[..amount]);
+        }
+        self.reader.consume(amount);
+    }
+}
+
+/// Opens a day's input to be read as a [`Stream`].
+pub fn open(day: u8, source: &Source) -> Result<Stream, InputError> {
+    let file = |path: &Path| {
+        let reader = File::open(path).map(|f| Box::new(BufReader::new(f)) as Box<dyn BufRead>);
+        (format!("`{}`", path.display()), reader)
+    };
+    let (origin, result) = match source {
+        Source::Bundled => file(&bundled_path(day)),
+        Source::File(path) => file(path),
+        Source::Stdin => ("stdin".to_string(), Ok(Box::new(io::stdin().lock()) as _)),
+    };
+
+    match result {
+        Ok(reader) => Ok(Stream {
+            day,
+            origin,
+            reader,
+            hash: FNV_OFFSET,
+        }),
+        Err(error) => Err(InputError { day, origin, error }),
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+
+/// Goes on with a FNV-1a `hash` over `bytes`.
+fn fnv(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// A 64-bit FNV-1a hash of the input, so results can be traced back to the
+/// input they came from.
+pub fn hash(input: &str) -> u64 {
+    fnv(FNV_OFFSET, input.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled() {
+        for day in 1..=25 {
+            assert!(load(day, &Source::Bundled).is_ok(), "day {}", day);
+        }
+    }
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(""), 0xcbf29ce484222325);
+        assert_eq!(hash("a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_stream() {
+        let mut stream = open(1, &Source::Bundled).unwrap();
+        let mut lines = 0;
+        let mut line = String::new();
+        while stream.read_line(&mut line).unwrap() > 0 {
+            lines += 1;
+        }
+        assert_eq!(line, load(1, &Source::Bundled).unwrap());
+        assert_eq!(stream.hash(), hash(&line));
+        assert!(lines > 1);
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let error = load(3, &Source::from_arg("no/such/input.txt")).unwrap_err();
+        assert_eq!(error.error.kind(), io::ErrorKind::NotFound);
+        assert!(error
+            .to_string()
+            .starts_with("day 3: cannot read input from"));
+    }
+}
@@ -0,0 +1,18 @@
+pub mod answers;
+pub mod bench;
+pub mod budget;
+pub mod days;
+pub mod differential;
+pub mod error;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod lint;
+pub mod memory;
+pub mod panics;
+pub mod params;
+pub mod pool;
+pub mod repl;
+pub mod runner;
+pub mod solution;
+pub mod trace;
@@ -0,0 +1,263 @@
+use std::{cmp::Reverse, collections::HashMap, ops::RangeInclusive};
+
+use crate::error::{Line, ParseError};
+
+/// A run of lines in a day's input; sections are separated by blank lines.
+pub struct Section {
+    /// What one of the section's lines holds, like "a row of crates".
+    pub line: &'static str,
+    /// The characters `chars` allows, like "a digit or `-`".
+    pub allowed: &'static str,
+    pub chars: fn(char) -> bool,
+    /// How many lines the section has.
+    pub count: RangeInclusive<usize>,
+    /// Whether every line is equally wide, like a grid's rows. Their trailing
+    /// spaces are then part of the row rather than a problem.
+    pub grid: bool,
+    /// Whether the section repeats to the end of the input, like day 1's
+    /// elves. Only the last section can repeat.
+    pub repeats: bool,
+}
+
+impl Section {
+    /// Any number of lines of printable text, which the struct update syntax
+    /// can narrow down.
+    pub const TEXT: Section = Section {
+        line: "a line",
+        allowed: "a printable ASCII character",
+        chars: printable,
+        count: 1..=usize::MAX,
+        grid: false,
+        repeats: false,
+    };
+}
+
+pub fn printable(c: char) -> bool {
+    c.is_ascii_graphic() || c == ' '
+}
+
+fn is_blank(line: &Line) -> bool {
+    line.text.trim().is_empty()
+}
+
+/// The width most of `lines` have, so that a single short row is the one
+/// reported; the first line's width wins a tie.
+fn common_width(lines: &[Line]) -> usize {
+    let mut counts = HashMap::new();
+    for (i, line) in lines.iter().enumerate() {
+        counts.entry(line.text.chars().count()).or_insert((0, i)).0 += 1;
+    }
+    let (width, _) = counts
+        .into_iter()
+        .max_by_key(|&(_, (count, first))| (count, Reverse(first)))
+        .unwrap();
+    width
+}
+
+/// Problems within the lines of one section: widths, characters and
+/// trailing whitespace.
+fn check_lines(section: &Section, lines: &[Line], problems: &mut Vec<ParseError>) {
+    let width = common_width(lines);
+    for line in lines {
+        let text = if section.grid {
+            line.text
+        } else {
+            line.text.trim_end()
+        };
+
+        if section.grid && text.chars().count() != width {
+            let found = text.chars().count();
+            problems.push(ParseError::new(
+                line.number,
+                found.min(width) + 1,
+                &format!("a row {} wide", width),
+                &format!("a row {} wide", found),
+            ));
+        }
+        if let Some((i, c)) = text.char_indices().find(|&(_, c)| !(section.chars)(c)) {
+            problems.push(line.error(&text[i..i + c.len_utf8()], section.allowed));
+        }
+        if text.len() < line.text.len() {
+            problems.push(line.error(&line.text[text.len()..], "end of line"));
+        }
+    }
+}
+
+/// Checks the layout of an input against a day's `sections`: how many
+/// sections there are, how many lines each has, their widths and characters.
+/// Windows line endings and trailing whitespace are reported for every day.
+/// Returns every problem found, in input order.
+pub fn check(sections: &[Section], input: &str) -> Vec<ParseError> {
+    if input.trim().is_empty() {
+        return vec![ParseError::end_of_input(0, "a puzzle input")];
+    }
+
+    let mut problems = vec![];
+    let mut crlf = vec![];
+    let lines = input
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(i, text)| {
+            let text = text.strip_suffix('\n').unwrap_or(text);
+            let text = text
+                .strip_suffix('\r')
+                .inspect(|_| crlf.push(i))
+                .unwrap_or(text);
+            Line {
+                number: i + 1,
+                text,
+            }
+        })
+        .collect::<Vec<_>>();
+    if let Some(&first) = crlf.first() {
+        problems.push(ParseError::new(
+            first + 1,
+            lines[first].text.chars().count() + 1,
+            "`\\n` line endings",
+            &format!("`\\r\\n` ({} in all)", crlf.len()),
+        ));
+    }
+
+    // Blank lines at the end are harmless, even ones holding whitespace;
+    // elsewhere, one separates sections.
+    let end = lines.iter().rposition(|line| !is_blank(line)).unwrap() + 1;
+    if sections.is_empty() {
+        check_lines(&Section::TEXT, &lines[..end], &mut problems);
+        problems.sort_by_key(|p| (p.line, p.column));
+        return problems;
+    }
+
+    for line in lines[..end]
+        .iter()
+        .filter(|line| is_blank(line) && !line.text.is_empty())
+    {
+        problems.push(line.error(line.text, "end of line"));
+    }
+
+    let mut rest = &lines[..end];
+    let mut index = 0;
+    while !rest.is_empty() {
+        let Some(section) = sections
+            .get(index)
+            .or_else(|| sections.last().filter(|s| s.repeats))
+        else {
+            problems.push(rest[0].error(rest[0].text, "end of input"));
+            break;
+        };
+        if is_blank(&rest[0]) {
+            problems.push(ParseError::new(
+                rest[0].number,
+                1,
+                section.line,
+                "another blank line",
+            ));
+            rest = &rest[1..];
+            continue;
+        }
+
+        let len = rest.iter().position(is_blank).unwrap_or(rest.len());
+        let (lines, after) = rest.split_at(len);
+        if len > *section.count.end() {
+            let extra = &lines[*section.count.end()];
+            problems.push(extra.error(extra.text, "a blank line"));
+        } else if len < *section.count.start() {
+            let found = if after.is_empty() {
+                "end of input"
+            } else {
+                "a blank line"
+            };
+            problems.push(ParseError::new(
+                lines[len - 1].number + 1,
+                1,
+                section.line,
+                found,
+            ));
+        }
+        check_lines(section, lines, &mut problems);
+
+        rest = after.get(1..).unwrap_or_default();
+        index += 1;
+    }
+
+    if let Some(missing) = sections.get(index) {
+        problems.push(ParseError::end_of_input(
+            end,
+            &format!("a blank line and then {}", missing.line),
+        ));
+    }
+
+    problems.sort_by_key(|p| (p.line, p.column));
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    const SECTIONS: [Section; 2] = [
+        Section {
+            line: "a row of the grid",
+            allowed: "`.` or `#`",
+            chars: |c| c == '.' || c == '#',
+            grid: true,
+            ..Section::TEXT
+        },
+        Section {
+            line: "a pair of numbers",
+            allowed: "a digit or a space",
+            chars: |c| c.is_ascii_digit() || c == ' ',
+            count: 2..=2,
+            repeats: true,
+            ..Section::TEXT
+        },
+    ];
+
+    fn problems(input: &str) -> Vec<String> {
+        check(&SECTIONS, input)
+            .into_iter()
+            .map(|p| format!("{}:{} {} / {}", p.line, p.column, p.expected, p.found))
+            .collect()
+    }
+
+    #[test]
+    fn test_clean() {
+        assert!(problems("..#\n#..\n\n1 2\n3 4\n\n5 6\n7 8\n\n").is_empty());
+        assert!(problems("..#\n#..\n\n1 2\n3 4\n  \n\t").is_empty());
+        assert!(check(&[], "text\n \n").is_empty());
+    }
+
+    #[test]
+    fn test_problems() {
+        assert_eq!(
+            problems("..#\n#.\n.#.\n\n1 2 \n3x4\n5 6\n\n\n7 8"),
+            [
+                "2:3 a row 3 wide / a row 2 wide",
+                "5:4 end of line / ` `",
+                "6:2 a digit or a space / `x`",
+                "7:1 a blank line / `5 6`",
+                "9:1 a pair of numbers / another blank line",
+                "11:1 a pair of numbers / end of input",
+            ]
+        );
+        assert_eq!(
+            problems("..#\r\n#..\r\n"),
+            [
+                "1:4 `\\n` line endings / `\\r\\n` (2 in all)",
+                "3:1 a blank line and then a pair of numbers / end of input",
+            ]
+        );
+        assert_eq!(problems(" \n"), ["1:1 a puzzle input / end of input"]);
+    }
+
+    #[test]
+    fn test_days() {
+        for day in days::all() {
+            for seed in 0..3 {
+                let input = day.generate(seed, 4);
+                let problems = day.lint(&input);
+                assert!(problems.is_empty(), "{}\n{}", input, problems[0]);
+            }
+        }
+    }
+}
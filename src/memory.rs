@@ -0,0 +1,171 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::{
+        atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+/// What a [`measure`]d piece of code allocated.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct Usage {
+    /// Most bytes held at once, beyond what was held when it started.
+    pub peak: usize,
+    /// Bytes allocated in all, counting each growth of a reallocation.
+    pub allocated: usize,
+    pub allocations: usize,
+}
+
+/// The system allocator, counting what the threads inside [`measure`]
+/// allocate. Threads outside only pay for checking a thread-local flag.
+struct Counting;
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+static TRACKING: AtomicBool = AtomicBool::new(false);
+
+/// Bytes held, peak and totals of the current measurement.
+static HELD: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Whether this thread's allocations count toward the measurement.
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+}
+
+pub(crate) fn counting() -> bool {
+    COUNTING.try_with(Cell::get).unwrap_or(false)
+}
+
+/// Lets a [`pool`](crate::pool) thread count toward its caller's measurement.
+pub(crate) fn set_counting(counting: bool) {
+    COUNTING.set(counting);
+}
+
+fn record(change: isize, allocated: usize) {
+    if counting() {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(allocated, Ordering::Relaxed);
+        let held = HELD.fetch_add(change, Ordering::Relaxed) + change;
+        PEAK.fetch_max(held, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record(layout.size() as isize, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record(layout.size() as isize, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        if counting() {
+            HELD.fetch_sub(layout.size() as isize, Ordering::Relaxed);
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            let change = new_size as isize - layout.size() as isize;
+            record(change, change.max(0) as usize);
+        }
+        new
+    }
+}
+
+/// Whether the runner measures memory, as `--memory` asks.
+pub fn set_tracking(tracking: bool) {
+    TRACKING.store(tracking, Ordering::Relaxed);
+}
+
+pub fn tracking() -> bool {
+    TRACKING.load(Ordering::Relaxed)
+}
+
+/// Runs `f`, counting what it allocates on this thread and on the
+/// [`pool`](crate::pool) threads it starts. There is one set of counters,
+/// so measurements wait for each other.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    for counter in [&HELD, &PEAK] {
+        counter.store(0, Ordering::Relaxed);
+    }
+    for counter in [&ALLOCATED, &ALLOCATIONS] {
+        counter.store(0, Ordering::Relaxed);
+    }
+
+    COUNTING.set(true);
+    let result = f();
+    COUNTING.set(false);
+
+    let usage = Usage {
+        peak: PEAK.load(Ordering::Relaxed) as usize,
+        allocated: ALLOCATED.load(Ordering::Relaxed),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+    };
+    (result, usage)
+}
+
+/// A byte count in the largest binary unit it reaches, like `1.5 MiB`.
+pub fn bytes(n: usize) -> String {
+    let mut value = n as f64;
+    for unit in ["B", "KiB", "MiB"] {
+        if value < 1024.0 {
+            return match unit {
+                "B" => format!("{} B", n),
+                _ => format!("{:.1} {}", value, unit),
+            };
+        }
+        value /= 1024.0;
+    }
+    format!("{:.1} GiB", value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (_, usage) = measure(|| vec![0u8; 1000]);
+        assert_eq!(
+            usage,
+            Usage {
+                peak: 1000,
+                allocated: 1000,
+                allocations: 1
+            }
+        );
+
+        let (_, usage) = measure(|| {
+            drop(vec![0u8; 1000]);
+            vec![0u8; 500]
+        });
+        assert_eq!((usage.peak, usage.allocated), (1000, 1500));
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(bytes(1023), "1023 B");
+        assert_eq!(bytes(1536), "1.5 KiB");
+        assert_eq!(bytes(5 << 30), "5.0 GiB");
+    }
+}
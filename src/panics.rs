@@ -0,0 +1,36 @@
+use std::{cell::Cell, panic, sync::Once};
+
+thread_local! {
+    /// Whether panics on this thread are expected, and so go unreported.
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+pub(crate) fn quiet() -> bool {
+    QUIET.get()
+}
+
+/// Lets a [`pool`](crate::pool) thread report panics as its caller does.
+pub(crate) fn set_quiet(quiet: bool) {
+    QUIET.set(quiet);
+}
+
+/// Runs `f` with panics going unreported on this thread and the pool threads
+/// it starts, for callers that expect and catch them. The hook is installed
+/// once and left in place, passing every other thread's panics on to the hook
+/// it replaced.
+pub(crate) fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    static HOOK: Once = Once::new();
+
+    HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                hook(info)
+            }
+        }));
+    });
+    let outer = QUIET.replace(true);
+    let result = f();
+    QUIET.set(outer);
+    result
+}
@@ -0,0 +1,111 @@
+use std::ops::RangeInclusive;
+
+/// A number a day's parts take, like day 15's row, which can be set from the
+/// command line as `--name VALUE`.
+pub struct Param {
+    pub name: &'static str,
+    /// The puzzle's own value.
+    pub default: i64,
+    /// The values the day can work with.
+    pub range: RangeInclusive<i64>,
+    pub help: &'static str,
+}
+
+/// Values given for a day's [`Param`]s; the others keep their defaults.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct Params {
+    values: Vec<(&'static str, i64)>,
+}
+
+impl Params {
+    /// Sets the parameter called `name`, which must be one of `params`, to
+    /// `value`, which must be in its range.
+    pub fn set(&mut self, params: &[Param], name: &str, value: &str) -> Result<(), String> {
+        let param = params
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| format!("unknown option `--{}`", name))?;
+        match value.parse() {
+            Ok(value) if param.range.contains(&value) => {
+                self.values.retain(|(name, _)| *name != param.name);
+                self.values.push((param.name, value));
+                Ok(())
+            }
+            _ => Err(format!(
+                "--{} expects a number from {} to {}",
+                name,
+                param.range.start(),
+                param.range.end()
+            )),
+        }
+    }
+
+    /// The value set for `param`, or its default.
+    pub fn get<T: TryFrom<i64>>(&self, param: &Param) -> T {
+        let value = self
+            .values
+            .iter()
+            .find(|(name, _)| *name == param.name)
+            .map_or(param.default, |(_, value)| *value);
+        // `set` keeps values in the range, which the day picks to fit `T`.
+        T::try_from(value).unwrap_or_else(|_| panic!("--{} out of range", param.name))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Lists `params` for `--help`: each option, its default and what it does.
+pub fn help(params: &[Param]) -> String {
+    params
+        .iter()
+        .map(|p| format!("  --{:<16} {} (default {})\n", p.name, p.help, p.default))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: [Param; 2] = [
+        Param {
+            name: "rounds",
+            default: 20,
+            range: 0..=1000,
+            help: "rounds to play",
+        },
+        Param {
+            name: "row",
+            default: 10,
+            range: -5..=i32::MAX as i64,
+            help: "row to count",
+        },
+    ];
+
+    #[test]
+    fn test_get() {
+        let mut params = Params::default();
+        assert_eq!(params.get::<usize>(&PARAMS[0]), 20);
+        params.set(&PARAMS, "rounds", "7").unwrap();
+        params.set(&PARAMS, "row", "-5").unwrap();
+        params.set(&PARAMS, "rounds", "8").unwrap();
+        assert_eq!(params.get::<usize>(&PARAMS[0]), 8);
+        assert_eq!(params.get::<i32>(&PARAMS[1]), -5);
+    }
+
+    #[test]
+    fn test_set_errors() {
+        let mut params = Params::default();
+        assert_eq!(
+            params.set(&PARAMS, "round", "7"),
+            Err("unknown option `--round`".to_string())
+        );
+        assert_eq!(
+            params.set(&PARAMS, "rounds", "1001"),
+            Err("--rounds expects a number from 0 to 1000".to_string())
+        );
+        assert!(params.set(&PARAMS, "row", "x").is_err());
+        assert!(params.is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crate::{budget, memory, panics};
+
+/// How many threads [`map`] may use; 0 for one per available core.
+static WORKERS: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_workers(workers: usize) {
+    WORKERS.store(workers, Ordering::Relaxed);
+}
+
+pub fn workers() -> usize {
+    match WORKERS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        workers => workers,
+    }
+}
+
+/// Applies `f` to every item on up to [`workers`] threads, and returns the
+/// results in the items' order.
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    map_on(workers(), items, f)
+}
+
+/// Each thread takes the next item nobody has started yet, so one slow item
+/// doesn't hold up the rest. A panic in `f` is passed on to the caller.
+fn map_on<T: Sync, R: Send>(workers: usize, items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    if workers <= 1 || items.len() <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let counting = memory::counting();
+    let running = budget::current();
+    let quiet = panics::quiet();
+    let mut results = thread::scope(|scope| {
+        let handles = (0..workers.min(items.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    memory::set_counting(counting);
+                    budget::set_current(running.clone());
+                    panics::set_quiet(quiet);
+                    let mut done = vec![];
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        match items.get(i) {
+                            Some(item) => done.push((i, f(item))),
+                            None => return done,
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect::<Vec<_>>()
+    });
+
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order() {
+        let items = (0..100).collect::<Vec<u64>>();
+        let squares = items.iter().map(|n| n * n).collect::<Vec<_>>();
+        for workers in [1, 3, 200] {
+            assert_eq!(map_on(workers, &items, |n| n * n), squares);
+        }
+    }
+
+    #[test]
+    fn test_memory() {
+        let items = [100, 200, 300];
+        let (_, usage) = memory::measure(|| map_on(3, &items, |&n| vec![0u8; n].len()));
+        assert!(usage.allocated >= 600, "{:?}", usage);
+    }
+
+    #[test]
+    fn test_budget() {
+        let budget = budget::Budget::unlimited();
+        budget.cancel();
+        let (spent, stopped) =
+            budget::limit(&budget, || map_on(3, &[1, 2, 3], |_| budget::spent()));
+        assert_eq!((spent, stopped), (vec![true; 3], true));
+    }
+
+    #[test]
+    fn test_quiet() {
+        panics::set_quiet(true);
+        let quiet = map_on(3, &[1, 2, 3], |_| panics::quiet());
+        panics::set_quiet(false);
+        assert_eq!(quiet, [true; 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "item 7")]
+    fn test_panic() {
+        map_on(4, &[1, 7, 3], |&n| assert_ne!(n, 7, "item {}", n));
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    io::{self, BufRead, Write},
+    time::Instant,
+};
+
+use crate::{
+    error::ParseError,
+    params::{self, Params},
+    solution::{Day, Parsed},
+};
+
+/// A command a day's [`Model`] answers.
+pub struct Query {
+    pub name: &'static str,
+    /// The arguments it takes, optional ones in brackets: `["KEY", "[DEPTH]"]`.
+    pub args: &'static [&'static str],
+    pub help: &'static str,
+}
+
+/// A day's parsed input, kept for a REPL session to answer queries about.
+pub trait Model {
+    fn queries(&self) -> &'static [Query];
+
+    /// Answers the query called `name`, one of [`Model::queries`], with
+    /// as many `args` as it takes; or explains what was wrong with them.
+    fn query(&self, name: &str, args: &[&str]) -> Result<String, String>;
+}
+
+const BUILT_INS: [(&str, &str); 4] = [
+    ("part 1|2", "solves a part with the parameters set so far"),
+    (
+        "set NAME VALUE",
+        "sets one of the day's parameters, if it has any",
+    ),
+    ("help", "lists the commands"),
+    ("quit", "ends the session, as does the end of input"),
+];
+
+/// A session exploring one day's input, which is parsed once at the start.
+pub struct Repl {
+    day: Day,
+    parsed: Parsed,
+    model: Option<Box<dyn Model>>,
+    params: Params,
+}
+
+impl Repl {
+    pub fn new(day: Day, input: &str) -> Result<Self, ParseError> {
+        let parsed = day.parse(input)?;
+        let params = Params::default();
+        let model = day.model(&parsed, &params);
+        Ok(Self {
+            day,
+            parsed,
+            model,
+            params,
+        })
+    }
+
+    fn help(&self) -> String {
+        let mut result = String::from("commands:\n");
+        for (command, help) in BUILT_INS {
+            result += &format!("  {:<18} {}\n", command, help);
+        }
+        if let Some(model) = &self.model {
+            result += &format!("day {} queries:\n", self.day.number);
+            for query in model.queries() {
+                let usage = [&[query.name], query.args].concat().join(" ");
+                result += &format!("  {:<18} {}\n", usage, query.help);
+            }
+        }
+        if !self.day.params.is_empty() {
+            result += &format!("day {} parameters:\n", self.day.number);
+            result += &params::help(self.day.params);
+        }
+        result
+    }
+
+    fn solve(&self, part: &str) -> Result<String, String> {
+        let part = match part {
+            "1" => 1,
+            "2" => 2,
+            _ => return Err("part expects 1 or 2".to_string()),
+        };
+        let start = Instant::now();
+        match self.day.solve_parsed(part, &self.parsed, &self.params) {
+            Ok(Some(answer)) => Ok(format!("{}\n({:.2?})\n", answer.compact(), start.elapsed())),
+            Ok(None) => Err(format!("day {} has no part {}", self.day.number, part)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    fn query(&self, name: &str, args: &[&str]) -> Result<String, String> {
+        let unknown = || format!("unknown command `{}`; try `help`", name);
+        let model = self.model.as_ref().ok_or_else(unknown)?;
+        let query = model
+            .queries()
+            .iter()
+            .find(|q| q.name == name)
+            .ok_or_else(unknown)?;
+
+        let required = query.args.iter().filter(|a| !a.starts_with('[')).count();
+        if !(required..=query.args.len()).contains(&args.len()) {
+            return Err(format!(
+                "usage: {}",
+                [&[name], query.args].concat().join(" ")
+            ));
+        }
+        model.query(name, args).map(|answer| answer + "\n")
+    }
+
+    /// Answers one line of input, a built-in command or one of the day's
+    /// queries; blank lines answer nothing.
+    pub fn eval(&mut self, line: &str) -> Result<String, String> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        match words[..] {
+            [] => Ok(String::new()),
+            ["help"] => Ok(self.help()),
+            ["part", part] => self.solve(part),
+            ["set", name, value] => {
+                let name = name.trim_start_matches("--");
+                self.params.set(self.day.params, name, value)?;
+                self.model = self.day.model(&self.parsed, &self.params);
+                Ok(String::new())
+            }
+            [name, ref args @ ..] => self.query(name, args),
+        }
+    }
+
+    /// Answers `commands` line by line until `quit` or their end. With
+    /// `prompt`, for a terminal, each line is asked for.
+    pub fn run(
+        &mut self,
+        commands: impl BufRead,
+        out: &mut impl Write,
+        prompt: bool,
+    ) -> io::Result<()> {
+        let mut lines = commands.lines();
+        loop {
+            if prompt {
+                write!(out, "day {}> ", self.day.number)?;
+                out.flush()?;
+            }
+            let Some(line) = lines.next().transpose()? else {
+                return Ok(());
+            };
+            if matches!(line.trim(), "quit" | "exit") {
+                return Ok(());
+            }
+            match self.eval(&line) {
+                Ok(answer) => write!(out, "{}", answer)?,
+                Err(e) => writeln!(out, "error: {}", e)?,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    static INPUT: &str = include_str!("days/day07/test_input.txt");
+
+    #[test]
+    fn test_eval() {
+        let mut repl = Repl::new(days::get(7).unwrap(), INPUT).unwrap();
+        assert_eq!(repl.eval("size /a"), Ok("94853\n".to_string()));
+        assert!(repl.eval("part 1").unwrap().starts_with("95437\n"));
+        assert!(repl
+            .eval("help")
+            .unwrap()
+            .contains("day 7 queries:\n  size PATH"));
+        assert_eq!(repl.eval("size"), Err("usage: size PATH".to_string()));
+        assert_eq!(
+            repl.eval("sizes /"),
+            Err("unknown command `sizes`; try `help`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_params() {
+        let mut repl = Repl::new(days::get(6).unwrap(), "mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
+        assert!(repl.eval("part 1").unwrap().starts_with("7\n"));
+        assert_eq!(repl.eval("set part1-window 14"), Ok(String::new()));
+        assert!(repl.eval("part 1").unwrap().starts_with("19\n"));
+        assert!(repl.eval("set part1-window 0").is_err());
+        assert!(repl.eval("eval").is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let mut repl = Repl::new(days::get(7).unwrap(), INPUT).unwrap();
+        let mut out = vec![];
+        repl.run(
+            "size /\n\nls /x\nquit\nsize /\n".as_bytes(),
+            &mut out,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "48381165\nerror: no directory `/x`\n"
+        );
+    }
+}
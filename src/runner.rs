@@ -0,0 +1,510 @@
+use std::{
+    env,
+    io::Read,
+    process::exit,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    budget, days,
+    error::{SolveError, StreamError},
+    input::{self, InputError, Source},
+    memory::{self, Usage},
+    params::{self, Params},
+    pool,
+    solution::{Answer, Day, Parsed},
+    trace::{self, Level},
+};
+
+pub struct Run {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<Answer, SolveError>,
+    /// How long parsing the input took, shared by the day's parts.
+    pub parse: Duration,
+    pub elapsed: Duration,
+    /// [`input::hash`] of the input that was solved.
+    pub input_hash: u64,
+    /// What the part allocated, when [`memory::tracking`].
+    pub memory: Option<Usage>,
+}
+
+/// An input parsed for the runs of its day's parts.
+struct Prepared {
+    day: Day,
+    parsed: Result<Parsed, SolveError>,
+    elapsed: Duration,
+    input_hash: u64,
+}
+
+fn prepare(day: &Day, input: &str) -> Prepared {
+    let start = Instant::now();
+    let parsed = day.parse(input).map_err(SolveError::from);
+    Prepared {
+        day: *day,
+        parsed,
+        elapsed: start.elapsed(),
+        input_hash: input::hash(input),
+    }
+}
+
+/// Solves `part` within the [`budget::for_part`], unless the day has no
+/// such part. A parse error is every part's answer.
+fn solve(prepared: &Prepared, part: u8, params: &Params) -> Option<Run> {
+    let day = &prepared.day;
+    let solve = || {
+        let start = Instant::now();
+        let answer = match &prepared.parsed {
+            Ok(parsed) => day
+                .solve_within(part, parsed, params, &budget::for_part())
+                .transpose(),
+            Err(e) => (part == 1 || part == 2).then(|| Err(e.clone())),
+        };
+        (answer, start.elapsed())
+    };
+    let ((answer, elapsed), memory) = if memory::tracking() {
+        let (result, usage) = memory::measure(solve);
+        (result, Some(usage))
+    } else {
+        (solve(), None)
+    };
+    Some(Run {
+        day: day.number,
+        part,
+        answer: answer?,
+        parse: prepared.elapsed,
+        elapsed,
+        input_hash: prepared.input_hash,
+        memory,
+    })
+}
+
+/// Parses `input` once and solves each of `parts` from it.
+pub fn run(day: &Day, parts: &[u8], input: &str, params: &Params) -> Vec<Run> {
+    let prepared = prepare(day, input);
+    parts
+        .iter()
+        .filter_map(|&part| solve(&prepared, part, params))
+        .collect()
+}
+
+/// Parses the input from `source` as it is read, for days that can, so
+/// that one too large to load still runs; other days load it as [`run`]
+/// does. Reading counts as parsing, and the parts are then solved as they
+/// are for a loaded input. Failing to read the input is an error for the
+/// caller, as it is for [`input::load`].
+pub fn run_streamed(
+    day: &Day,
+    parts: &[u8],
+    source: &Source,
+    params: &Params,
+) -> Result<Vec<Run>, InputError> {
+    let mut input = input::open(day.number, source)?;
+    let start = Instant::now();
+    let parsed = match day.parse_stream(&mut input) {
+        Some(Ok(parsed)) => Ok(parsed),
+        Some(Err(StreamError::Parse(e))) => Err(e.into()),
+        Some(Err(StreamError::Problems(problems))) => Err(SolveError::Problems(problems)),
+        Some(Err(StreamError::Read(e))) => return Err(input.error(e)),
+        None => {
+            let mut loaded = String::new();
+            if let Err(e) = input.read_to_string(&mut loaded) {
+                return Err(input.error(e));
+            }
+            return Ok(run(day, parts, &loaded, params));
+        }
+    };
+    let prepared = Prepared {
+        day: *day,
+        parsed,
+        elapsed: start.elapsed(),
+        input_hash: input.hash(),
+    };
+    Ok(parts
+        .iter()
+        .filter_map(|&part| solve(&prepared, part, params))
+        .collect())
+}
+
+/// Parses every input and then runs the parts of every day side by side on
+/// the [`pool`], and returns the runs in day and part order. Each run still
+/// times only itself, although with more workers than cores the runs slow
+/// each other down. Memory is measured one part at a time, so tracking it
+/// runs them in turn.
+pub fn run_all(inputs: &[(Day, String)], parts: &[u8], params: &Params) -> Vec<Run> {
+    let prepared = pool::map(inputs, |(day, input)| prepare(day, input));
+    let jobs = prepared
+        .iter()
+        .flat_map(|prepared| parts.iter().map(move |&part| (prepared, part)))
+        .collect::<Vec<_>>();
+    pool::map(&jobs, |&(prepared, part)| solve(prepared, part, params))
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// What the table shows in a run's answer column.
+fn shown(run: &Run) -> String {
+    match &run.answer {
+        Ok(answer) => answer.compact(),
+        Err(SolveError::TimedOut { .. }) => "timed out".to_string(),
+        Err(_) => "error".to_string(),
+    }
+}
+
+/// Lays runs out as a table, with answers in their [`Answer::compact`] form;
+/// multi-line answers continue under their own column. Runs with
+/// [`Run::memory`] add its columns.
+pub fn table(runs: &[Run]) -> String {
+    let shown = runs.iter().map(shown).collect::<Vec<_>>();
+    let width = shown
+        .iter()
+        .flat_map(|answer| answer.lines())
+        .map(|line| line.chars().count())
+        .chain(["answer".len()])
+        .max()
+        .unwrap();
+
+    let memory = |usage: Option<Usage>| match usage {
+        Some(u) => format!(
+            "  {:>10}  {:>10}  {:>7}",
+            memory::bytes(u.peak),
+            memory::bytes(u.allocated),
+            u.allocations
+        ),
+        None => String::new(),
+    };
+
+    let mut result = format!(
+        "{:>3}  {:>4}  {:<width$}  {:>12}  {:>12}",
+        "day", "part", "answer", "parse", "time"
+    );
+    if runs.iter().any(|run| run.memory.is_some()) {
+        result += &format!("  {:>10}  {:>10}  {:>7}", "peak", "allocated", "allocs");
+    }
+    result += "\n";
+    for (run, answer) in runs.iter().zip(&shown) {
+        let mut lines = answer.lines();
+        result += &format!(
+            "{:>3}  {:>4}  {:<width$}  {:>12}  {:>12}{}\n",
+            run.day,
+            run.part,
+            lines.next().unwrap_or_default(),
+            format!("{:.2?}", run.parse),
+            format!("{:.2?}", run.elapsed),
+            memory(run.memory),
+        );
+        for line in lines {
+            result += &format!("{:>3}  {:>4}  {line}\n", "", "");
+        }
+    }
+    result
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result += "\\\"",
+            '\\' => result += "\\\\",
+            '\n' => result += "\\n",
+            c if c.is_control() => result += &format!("\\u{:04x}", c as u32),
+            c => result.push(c),
+        }
+    }
+    result + "\""
+}
+
+/// An answer as a JSON number if it is an integer, or else as a string.
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Integer(n) => n.to_string(),
+        answer => json_string(&answer.to_string()),
+    }
+}
+
+/// One run as a line of JSON. Answers that are integers are written as JSON
+/// numbers, any others (like day 5's crates or day 10's image) as strings;
+/// a run that failed has an `error` instead of an `answer`, and one that
+/// timed out with an answer so far also has it as `best`. `parse_ns` is the
+/// day's parsing, `elapsed_ns` the part's own solving. Measured runs add
+/// `peak_bytes`, `allocated_bytes` and `allocations`.
+pub fn json(run: &Run) -> String {
+    let result = match &run.answer {
+        Ok(answer) => format!("\"answer\":{}", json_answer(answer)),
+        Err(
+            e @ SolveError::TimedOut {
+                best: Some(best), ..
+            },
+        ) => format!(
+            "\"error\":{},\"best\":{}",
+            json_string(&e.to_string()),
+            json_answer(best)
+        ),
+        Err(e) => format!("\"error\":{}", json_string(&e.to_string())),
+    };
+    let memory = match run.memory {
+        Some(u) => format!(
+            ",\"peak_bytes\":{},\"allocated_bytes\":{},\"allocations\":{}",
+            u.peak, u.allocated, u.allocations
+        ),
+        None => String::new(),
+    };
+    format!(
+        "{{\"day\":{},\"part\":{},{},\"parse_ns\":{},\"elapsed_ns\":{},\"input_hash\":\"{:016x}\"{}}}",
+        run.day,
+        run.part,
+        result,
+        run.parse.as_nanos(),
+        run.elapsed.as_nanos(),
+        run.input_hash,
+        memory
+    )
+}
+
+/// All the answers of `runs` as a Markdown report, under a heading for each
+/// day. Answers are given in full, images in code blocks to keep their rows
+/// apart; parts without one give their error instead.
+pub fn report(runs: &[Run]) -> String {
+    let mut result = String::from("# Advent of Code 2022\n");
+    let mut day = None;
+    for run in runs {
+        if day != Some(run.day) {
+            day = Some(run.day);
+            result += &format!("\n## Day {}\n\n", run.day);
+        }
+        result += &match &run.answer {
+            Ok(Answer::Image(image)) => format!("- Part {}:\n\n```\n{}\n```\n", run.part, image),
+            Ok(answer) => format!("- Part {}: {}\n", run.part, answer),
+            Err(e) => format!("- Part {}: no answer; {}\n", run.part, e),
+        };
+    }
+    result
+}
+
+/// The errors among `runs`, for reporting after their table.
+pub fn errors(runs: &[Run]) -> impl Iterator<Item = &SolveError> {
+    runs.iter().filter_map(|run| run.answer.as_ref().err())
+}
+
+/// Entry point of the per-day binaries: `<day> [--part 1|2] [--json] [-v|-vv]
+/// [--timeout SECS] [--PARAM VALUE]... [PATH|-]` prints each part's answer on
+/// its own line, or as a line of JSON with `--json`, reading the bundled
+/// input unless told otherwise.
+pub fn day_main(number: u8) {
+    let day = days::get(number).unwrap();
+    let usage = format!(
+        "usage: {} [--part 1|2] [--json] [-v|-vv] [--timeout SECS] [--PARAM VALUE]... [PATH|-]\n{}",
+        number,
+        params::help(day.params)
+    );
+    let fail = |message: &str| -> ! {
+        eprintln!("{}\n{}", message, usage);
+        exit(2);
+    };
+
+    let mut json_output = false;
+    let mut source = Source::Bundled;
+    let mut parts = vec![1, 2];
+    let mut params = Params::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => match args.next().as_deref() {
+                Some("1") => parts = vec![1],
+                Some("2") => parts = vec![2],
+                _ => fail("--part expects 1 or 2"),
+            },
+            "--json" => json_output = true,
+            "-v" => trace::set_level(Level::Steps),
+            "-vv" => trace::set_level(Level::States),
+            "--timeout" => match args
+                .next()
+                .and_then(|s| Duration::try_from_secs_f64(s.parse().ok()?).ok())
+                .filter(|timeout| !timeout.is_zero())
+            {
+                Some(timeout) => budget::set_timeout(timeout),
+                None => fail("--timeout expects seconds above 0"),
+            },
+            "-h" | "--help" => {
+                print!("{}", usage);
+                return;
+            }
+            name if name.starts_with("--") => {
+                let value = args.next().unwrap_or_default();
+                if let Err(e) = params.set(day.params, &name[2..], &value) {
+                    fail(&e);
+                }
+            }
+            arg => source = Source::from_arg(arg),
+        }
+    }
+
+    let runs = if !matches!(source, Source::Bundled) {
+        run_streamed(&day, &parts, &source, &params)
+    } else {
+        input::load(number, &source).map(|input| run(&day, &parts, &input, &params))
+    };
+    let runs = match runs {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    };
+
+    for run in runs {
+        if json_output {
+            println!("{}", json(&run));
+        }
+        match run.answer {
+            Ok(answer) if !json_output => println!("{}", answer),
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("{}", e);
+                exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+
+    fn run(answer: Result<Answer, SolveError>) -> Run {
+        Run {
+            day: 10,
+            part: 2,
+            answer,
+            parse: Duration::from_micros(4),
+            elapsed: Duration::from_micros(15),
+            input_hash: 0xaf63dc4c8601ec8c,
+            memory: None,
+        }
+    }
+
+    fn measured() -> Run {
+        Run {
+            memory: Some(Usage {
+                peak: 2048,
+                allocated: 4096,
+                allocations: 3,
+            }),
+            ..run(Ok(Answer::Integer(13740)))
+        }
+    }
+
+    #[test]
+    fn test_run_all() {
+        let inputs = [5, 1, 2].map(|number| {
+            let day = days::get(number).unwrap();
+            (day, day.generate(0, 3))
+        });
+        let runs = run_all(&inputs, &[1, 2], &Params::default());
+        let order = runs
+            .iter()
+            .map(|run| (run.day, run.part))
+            .collect::<Vec<_>>();
+        assert_eq!(order, [(5, 1), (5, 2), (1, 1), (1, 2), (2, 1), (2, 2)]);
+        for (run, (day, input)) in runs.chunks(2).zip(&inputs) {
+            let answers = [1, 2].map(|part| day.solve(part, input).unwrap().unwrap());
+            assert_eq!(
+                run.iter()
+                    .map(|r| r.answer.clone().unwrap())
+                    .collect::<Vec<_>>(),
+                answers
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_streamed() {
+        let source = Source::File(input::day_file(1, "test_input.txt"));
+        let params = Params::default();
+        for number in [1, 2] {
+            let day = days::get(number).unwrap();
+            let source = Source::File(input::day_file(number, "test_input.txt"));
+            let input = input::load(number, &source).unwrap();
+            let loaded = super::run(&day, &[1, 2], &input, &params);
+            let streamed = run_streamed(&day, &[1, 2], &source, &params).unwrap();
+            assert_eq!(streamed.len(), 2);
+            for (loaded, streamed) in loaded.iter().zip(&streamed) {
+                assert_eq!(streamed.answer, loaded.answer);
+                assert_eq!(streamed.input_hash, loaded.input_hash);
+            }
+        }
+
+        let day = days::get(1).unwrap();
+        let streamed = run_streamed(&day, &[2], &source, &params).unwrap();
+        assert_eq!(streamed.iter().map(|run| run.part).collect::<Vec<_>>(), [2]);
+        assert!(streamed[0].elapsed > Duration::ZERO);
+
+        let missing = Source::from_arg("no/such/input.txt");
+        assert!(run_streamed(&day, &[1], &missing, &params).is_err());
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            json(&run(Ok(Answer::Integer(13740)))),
+            r#"{"day":10,"part":2,"answer":13740,"parse_ns":4000,"elapsed_ns":15000,"input_hash":"af63dc4c8601ec8c"}"#
+        );
+        let text = Answer::Text("A\n\"B\"".to_string());
+        assert!(json(&run(Ok(text))).contains(r#""answer":"A\n\"B\"""#));
+        let error = ParseError::new(1, 2, "a number", "`x`").in_day(10);
+        assert!(json(&run(Err(error.into())))
+            .contains(r#""error":"day 10, line 1, column 2: expected a number, found `x`""#));
+        let timed_out = SolveError::TimedOut {
+            day: 10,
+            part: 2,
+            best: Some(Answer::Integer(13000)),
+        };
+        assert!(json(&run(Err(timed_out))).contains(
+            r#""error":"day 10, part 2: timed out, best answer so far 13000","best":13000,"#
+        ));
+        assert!(json(&measured()).ends_with(
+            r#""input_hash":"af63dc4c8601ec8c","peak_bytes":2048,"allocated_bytes":4096,"allocations":3}"#
+        ));
+    }
+
+    #[test]
+    fn test_report() {
+        let image = Answer::Image("#.\n.#".to_string());
+        let runs = [
+            Run {
+                day: 5,
+                part: 1,
+                ..run(Ok(Answer::Text("CMZ".to_string())))
+            },
+            run(Ok(Answer::Integer(13140))),
+            run(Ok(image)),
+        ];
+        assert_eq!(
+            report(&runs),
+            "# Advent of Code 2022\n\
+             \n## Day 5\n\n- Part 1: CMZ\n\
+             \n## Day 10\n\n- Part 2: 13140\n- Part 2:\n\n```\n#.\n.#\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_table_image() {
+        let table = table(&[run(Ok(Answer::Image("##.\n#.#".to_string())))]);
+        assert!(table
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with(" 10     2  █▀▄     "));
+    }
+
+    #[test]
+    fn test_table_memory() {
+        let table = table(&[measured(), run(Ok(Answer::Integer(7)))]);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert!(lines[0].ends_with("time        peak   allocated   allocs"));
+        assert!(lines[1].ends_with("2.0 KiB     4.0 KiB        3"));
+        assert!(lines[2].ends_with("15.00µs"));
+    }
+}
@@ -0,0 +1,290 @@
+use std::{any::Any, fmt, io::BufRead};
+
+use crate::{
+    budget::{self, Budget},
+    error::{ParseError, SolveError, StreamError},
+    generate::Rng,
+    lint::{self, Section},
+    params::{Param, Params},
+    repl::Model,
+};
+
+/// A part's answer, in the form the puzzle asks for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    /// Text to type in as it is, like day 5's crates.
+    Text(String),
+    /// Rows of `#` and `.` spelling out capital letters, like day 10's screen.
+    Image(String),
+}
+
+macro_rules! integer_answers {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Self::Integer(n as i128)
+            }
+        }
+    )*};
+}
+
+integer_answers!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl Answer {
+    /// The answer for a terminal: images take half the rows, drawing two
+    /// pixels in each character with block elements.
+    pub fn compact(&self) -> String {
+        let Self::Image(image) = self else {
+            return self.to_string();
+        };
+        let rows = image.lines().collect::<Vec<_>>();
+        rows.chunks(2)
+            .map(|pair| {
+                let lit = |row: Option<&&str>, x| row.and_then(|r| r.chars().nth(x)) == Some('#');
+                let width = pair
+                    .iter()
+                    .map(|row| row.chars().count())
+                    .max()
+                    .unwrap_or(0);
+                (0..width)
+                    .map(|x| match (lit(pair.first(), x), lit(pair.get(1), x)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The full answer, as it is submitted or saved to a file.
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{}", n),
+            Self::Text(text) | Self::Image(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// A day's puzzle: the input parsed once, then both parts solved from it.
+pub trait Solution {
+    /// Day of the calendar, starting at 1.
+    const DAY: u8;
+
+    /// The input as the parts work on it.
+    type Parsed: Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+
+    /// Parses `input` as it is read, for days whose inputs can be too large
+    /// to load whole, keeping only what the parts need. `None` for days that
+    /// parse loaded inputs only, which read nothing here.
+    fn parse_stream(_input: &mut dyn BufRead) -> Option<Result<Self::Parsed, StreamError>> {
+        None
+    }
+
+    /// Numbers the parts take from `params`, with the puzzle's values as
+    /// defaults.
+    const PARAMS: &'static [Param] = &[];
+
+    /// The layout of the input, for [`Day::lint`]. Without one, only the
+    /// checks that apply to every day are made.
+    const SECTIONS: &'static [Section] = &[];
+
+    /// Parts that can search for a long time check [`budget::spent`], and
+    /// when it is return the best answer they have, or an error if none.
+    fn part1(parsed: &Self::Parsed, params: &Params) -> Result<Answer, SolveError>;
+
+    /// `Ok(None)` for days that only have one part.
+    fn part2(parsed: &Self::Parsed, params: &Params) -> Result<Option<Answer>, SolveError>;
+
+    /// A random input in the puzzle's format that the parts can solve.
+    /// `size` counts the input's main records (lines, monkeys, valves…);
+    /// each day documents what it counts.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+
+    /// A slow but straightforward solution of `part`, to check the fast one
+    /// against. `Ok(None)` for parts without one, and for inputs too large
+    /// for it.
+    fn reference(_part: u8, _input: &str) -> Result<Option<String>, ParseError> {
+        Ok(None)
+    }
+
+    /// A report on `input` for `aoc stats`, read a line at a time like
+    /// [`Solution::parse_stream`] reads it, so that inputs too large to load
+    /// can be reported on. `None` for days without one.
+    fn stats(_input: &mut dyn BufRead, _params: &Params) -> Option<Result<String, StreamError>> {
+        None
+    }
+
+    /// The parsed input as `params` read it, for `aoc repl` to answer the
+    /// day's queries about; built again whenever they are set. `None` for
+    /// days without any.
+    fn model(_parsed: &Self::Parsed, _params: &Params) -> Option<Box<dyn Model>> {
+        None
+    }
+}
+
+/// A day's [`Solution::Parsed`] input, type-erased like [`Day`], which only
+/// the parts of the day that parsed it can solve.
+pub struct Parsed {
+    day: u8,
+    value: Box<dyn Any + Send + Sync>,
+}
+
+impl Parsed {
+    fn get<S: Solution>(&self) -> &S::Parsed {
+        self.value
+            .downcast_ref()
+            .unwrap_or_else(|| panic!("day {} cannot solve day {}'s input", S::DAY, self.day))
+    }
+}
+
+/// Type-erased handle to a [`Solution`], so days can be listed and run
+/// without naming their types.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    pub params: &'static [Param],
+    sections: &'static [Section],
+    parse: fn(&str) -> Result<Parsed, ParseError>,
+    parse_stream: fn(&mut dyn BufRead) -> Option<Result<Parsed, StreamError>>,
+    part1: fn(&Parsed, &Params) -> Result<Answer, SolveError>,
+    part2: fn(&Parsed, &Params) -> Result<Option<Answer>, SolveError>,
+    generate: fn(&mut Rng, usize) -> String,
+    reference: fn(u8, &str) -> Result<Option<String>, ParseError>,
+    stats: fn(&mut dyn BufRead, &Params) -> Option<Result<String, StreamError>>,
+    model: fn(&Parsed, &Params) -> Option<Box<dyn Model>>,
+}
+
+impl Day {
+    pub fn of<S: Solution>() -> Self {
+        Self {
+            number: S::DAY,
+            params: S::PARAMS,
+            sections: S::SECTIONS,
+            parse: |input| {
+                Ok(Parsed {
+                    day: S::DAY,
+                    value: Box::new(S::parse(input)?),
+                })
+            },
+            parse_stream: |input| {
+                Some(S::parse_stream(input)?.map(|value| Parsed {
+                    day: S::DAY,
+                    value: Box::new(value),
+                }))
+            },
+            part1: |parsed, params| S::part1(parsed.get::<S>(), params),
+            part2: |parsed, params| S::part2(parsed.get::<S>(), params),
+            generate: S::generate,
+            reference: S::reference,
+            stats: S::stats,
+            model: |parsed, params| S::model(parsed.get::<S>(), params),
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        (self.parse)(input).map_err(|e| e.in_day(self.number))
+    }
+
+    /// Parses `input` as it is read, or returns `None` without reading any of
+    /// it if the day only parses loaded inputs.
+    pub fn parse_stream(&self, input: &mut dyn BufRead) -> Option<Result<Parsed, StreamError>> {
+        let result = (self.parse_stream)(input)?;
+        Some(result.map_err(|e| e.in_day(self.number)))
+    }
+
+    /// The day's report on `input`, or `None` without reading any of it if
+    /// the day has none.
+    pub fn stats(
+        &self,
+        input: &mut dyn BufRead,
+        params: &Params,
+    ) -> Option<Result<String, StreamError>> {
+        let result = (self.stats)(input, params)?;
+        Some(result.map_err(|e| e.in_day(self.number)))
+    }
+
+    /// Every problem with the layout of `input`, followed by the parse error
+    /// if the day's parser fails on a line the layout doesn't already explain.
+    pub fn lint(&self, input: &str) -> Vec<ParseError> {
+        let mut problems = lint::check(self.sections, input);
+        if let Err(e) = (self.parse)(input) {
+            if !problems.iter().any(|p| p.line == e.line) {
+                problems.push(e);
+            }
+        }
+        problems
+            .into_iter()
+            .map(|e| e.in_day(self.number))
+            .collect()
+    }
+
+    /// Parses `input` and solves `part` with the default parameters, or
+    /// returns `Ok(None)` if the day has no such part.
+    pub fn solve(&self, part: u8, input: &str) -> Result<Option<Answer>, SolveError> {
+        self.solve_parsed(part, &self.parse(input)?, &Params::default())
+    }
+
+    /// Solves `part` of an input this day has already parsed, so that
+    /// solving both parts parses only once.
+    pub fn solve_parsed(
+        &self,
+        part: u8,
+        parsed: &Parsed,
+        params: &Params,
+    ) -> Result<Option<Answer>, SolveError> {
+        let result = match part {
+            1 => (self.part1)(parsed, params).map(Some),
+            2 => (self.part2)(parsed, params),
+            _ => Ok(None),
+        };
+        result.map_err(|e| e.in_part(self.number, part))
+    }
+
+    /// Solves `part` like [`Day::solve_parsed`], but stops when `budget` is
+    /// spent, with the best answer the part had found by then.
+    pub fn solve_within(
+        &self,
+        part: u8,
+        parsed: &Parsed,
+        params: &Params,
+        budget: &Budget,
+    ) -> Result<Option<Answer>, SolveError> {
+        match budget::limit(budget, || self.solve_parsed(part, parsed, params)) {
+            (answer, false) => answer,
+            (answer, true) => Err(SolveError::TimedOut {
+                day: self.number,
+                part,
+                best: answer.ok().flatten(),
+            }),
+        }
+    }
+
+    /// Solves `part` with the day's reference solution, if it has one.
+    pub fn reference(&self, part: u8, input: &str) -> Result<Option<String>, ParseError> {
+        (self.reference)(part, input).map_err(|e| e.in_day(self.number))
+    }
+
+    pub fn model(&self, parsed: &Parsed, params: &Params) -> Option<Box<dyn Model>> {
+        (self.model)(parsed, params)
+    }
+
+    /// Generates an input from `seed`; the same seed always gives the same
+    /// input.
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+}
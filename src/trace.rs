@@ -0,0 +1,101 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// How much the simulation days report while they run, in increasing detail.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Level {
+    Off,
+    /// One line per step: a move, an instruction, a round, a rock…
+    Steps,
+    /// Every step, followed by a drawing of the state it leaves behind.
+    States,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+#[cfg(test)]
+thread_local! {
+    /// Tracing captured on this thread by [`capture`], and its level.
+    static CAPTURED: std::cell::RefCell<Option<(Level, String)>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Whether `level` is traced. [`trace!`](crate::trace!) checks this before
+/// formatting anything, so runs without tracing only pay for the check.
+pub fn enabled(level: Level) -> bool {
+    #[cfg(test)]
+    if CAPTURED.with_borrow(|c| c.as_ref().is_some_and(|(l, _)| *l >= level)) {
+        return true;
+    }
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Writes a traced line to stderr, keeping stdout for the answers.
+pub fn emit(args: fmt::Arguments) {
+    #[cfg(test)]
+    if CAPTURED
+        .with_borrow_mut(|c| c.as_mut().map(|(_, out)| *out += &format!("{}\n", args)))
+        .is_some()
+    {
+        return;
+    }
+    eprintln!("{}", args);
+}
+
+/// Runs `f` tracing at `level`, and returns what it traced on this thread.
+#[cfg(test)]
+pub fn capture<T>(level: Level, f: impl FnOnce() -> T) -> (T, String) {
+    CAPTURED.set(Some((level, String::new())));
+    let result = f();
+    let (_, out) = CAPTURED.take().unwrap();
+    (result, out)
+}
+
+/// Traces a line at a [`Level`] given by name: `trace!(Steps, "round {}", n)`.
+#[macro_export]
+macro_rules! trace {
+    ($level:ident, $($arg:tt)*) => {
+        if $crate::trace::enabled($crate::trace::Level::$level) {
+            $crate::trace::emit(format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simulate() {
+        for step in 1..=2 {
+            trace!(Steps, "step {}", step);
+            trace!(States, "[{}]", "#".repeat(step));
+        }
+    }
+
+    #[test]
+    fn test_levels() {
+        assert_eq!(capture(Level::Off, simulate).1, "");
+        assert_eq!(capture(Level::Steps, simulate).1, "step 1\nstep 2\n");
+        assert_eq!(
+            capture(Level::States, simulate).1,
+            "step 1\n[#]\nstep 2\n[##]\n"
+        );
+    }
+
+    #[test]
+    fn test_lazy() {
+        let mut formatted = false;
+        let mut spy = || {
+            formatted = true;
+            ""
+        };
+        capture(Level::Steps, || trace!(States, "{}", spy()));
+        assert!(!formatted);
+    }
+}
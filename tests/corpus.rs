@@ -0,0 +1,84 @@
+//! Golden tests over `tests/corpus`: every `dayNN/<case>/` directory holds an
+//! `input.txt` and an `answers.txt` in the same format as the recorded answers
+//! next to each day's solution. Only the parts listed in `answers.txt` are
+//! run, so a case can check just one part.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_2022::{
+    answers::{self, Answers, Verdict},
+    days,
+    params::Params,
+    runner,
+};
+
+fn subdirectories(path: &Path) -> Vec<PathBuf> {
+    let mut result = fs::read_dir(path)
+        .unwrap_or_else(|e| panic!("cannot list `{}`: {}", path.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+/// Runs one case, describing every way it went wrong.
+fn check_case(number: u8, case: &Path) -> Vec<String> {
+    let day = days::get(number).unwrap();
+    let read = |name| fs::read_to_string(case.join(name));
+    let (input, expected) = match (read("input.txt"), read("answers.txt")) {
+        (Ok(input), Ok(answers)) => (input, Answers::parse(&answers)),
+        _ => return vec!["needs both input.txt and answers.txt".to_string()],
+    };
+
+    let parts = [1, 2]
+        .into_iter()
+        .filter(|&part| expected.get(part).is_some())
+        .collect::<Vec<_>>();
+    if parts.is_empty() {
+        return vec!["answers.txt has no answers".to_string()];
+    }
+
+    let runs = runner::run(&day, &parts, &input, &Params::default());
+    if runs.len() < parts.len() {
+        return vec![format!("day {} has no part 2", number)];
+    }
+    runs.iter()
+        .filter_map(|run| match answers::check(run, &expected) {
+            Verdict::Wrong { expected, found } => Some(format!(
+                "part {}: expected {:?}, found {:?}",
+                run.part, expected, found
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn corpus() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+
+    let mut cases = 0;
+    let mut failures = vec![];
+    for day_dir in subdirectories(&root) {
+        let name = day_dir.file_name().unwrap().to_string_lossy();
+        let number = name
+            .strip_prefix("day")
+            .and_then(|n| n.parse().ok())
+            .filter(|&n| days::get(n).is_some())
+            .unwrap_or_else(|| panic!("`{}` is not a day directory like `day01`", name));
+
+        for case in subdirectories(&day_dir) {
+            cases += 1;
+            for failure in check_case(number, &case) {
+                failures.push(format!("{}: {}", case.display(), failure));
+            }
+        }
+    }
+
+    assert!(cases > 0, "no cases found under `{}`", root.display());
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}